@@ -28,11 +28,29 @@ pub fn env_feature_enabled(feature: &str) -> Option<bool> {
     }
 }
 
+// Whether the borrow checker should accept or reject the mutants of a `Mutation`
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExpectedOutcome {
+    Pass,
+    #[default]
+    Fail,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct MutatorData {
+    #[serde(default)]
+    pub expected: ExpectedOutcome,
     pub instances: i64,
     pub passed: i64,
     pub failed: i64,
     pub panicked: i64,
+    // Mutants that are not well-formed MIR, and so are not borrow checked
+    #[serde(default)]
+    pub invalid: i64,
+    // Mutants whose borrow check result differs from `expected`. These are surviving
+    // mutants if `expected` is `Fail`, and places where the PCG is more permissive
+    // than the borrow checker if `expected` is `Pass`.
+    #[serde(default)]
+    pub unexpected: i64,
     pub error_codes: HashSet<String>,
 }
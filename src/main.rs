@@ -3,19 +3,50 @@
 
 extern crate borrowck;
 
+use pcg_mutation_testing::ExpectedOutcome;
 use pcg_mutation_testing::MutatorData;
 
 use pcg_mutation_testing::mutator::Mutant;
 use pcg_mutation_testing::mutator::MutantRange;
 use pcg_mutation_testing::mutator::Mutation;
 use pcg_mutation_testing::mutator::Mutator;
+use pcg_mutation_testing::mutator::PhasePair;
 
+use pcg_mutation_testing::mutator::validate::validate_mutant;
+
+use pcg_mutation_testing::mutator::assign_to_borrowed::AssignToBorrowed;
+use pcg_mutation_testing::mutator::closure_capture::ConflictWithClosureCapture;
+use pcg_mutation_testing::mutator::double_mut_borrow::DoubleMutBorrow;
+use pcg_mutation_testing::mutator::drop_while_borrowed::DropWhileBorrowed;
+use pcg_mutation_testing::mutator::escaping_reference::EscapeViaArgument;
+use pcg_mutation_testing::mutator::escaping_reference::EscapeViaReturn;
 use pcg_mutation_testing::mutator::expiry_order::AbstractExpiryOrder;
 use pcg_mutation_testing::mutator::expiry_order::BorrowExpiryOrder;
+use pcg_mutation_testing::mutator::loop_back_edge::ConflictAcrossBackEdge;
 use pcg_mutation_testing::mutator::move_from_borrowed::MoveFromBorrowed;
+use pcg_mutation_testing::mutator::move_out_of_reference::MoveOutOfDeref;
+use pcg_mutation_testing::mutator::move_out_of_reference::MoveOutOfIndex;
 use pcg_mutation_testing::mutator::mutably_lend_shared::MutablyLendShared;
+use pcg_mutation_testing::mutator::partial_move::PartialMoveThenUseParent;
+use pcg_mutation_testing::mutator::partial_move::PartialMoveThenUseSibling;
+use pcg_mutation_testing::mutator::positive_control::ReadFromReadable;
+use pcg_mutation_testing::mutator::positive_control::WriteToExclusive;
+use pcg_mutation_testing::mutator::read_from_mutably_lent::ReadFromMutablyLent;
 use pcg_mutation_testing::mutator::read_from_write::ReadFromWriteOnly;
+use pcg_mutation_testing::mutator::reborrow_through_deref::MutablyReborrowShared;
+use pcg_mutation_testing::mutator::reborrow_through_deref::WriteThroughSharedReborrow;
+use pcg_mutation_testing::mutator::region_projection::ConflictWithRegionProjection;
+use pcg_mutation_testing::mutator::remote_place::WriteThroughMutArgument;
+use pcg_mutation_testing::mutator::remote_place::WriteThroughSharedArgument;
+use pcg_mutation_testing::mutator::sibling_borrow::MutablyBorrowSibling;
+use pcg_mutation_testing::mutator::statement_reorder::SwapPastRelease;
+use pcg_mutation_testing::mutator::storage_dead_while_borrowed::StorageDeadWhileBorrowed;
+use pcg_mutation_testing::mutator::write_through_shared::WriteThroughShared;
+use pcg_mutation_testing::mutator::write_to_relabelled::WriteToRelabelled;
 use pcg_mutation_testing::mutator::write_to_shared::WriteToShared;
+use pcg_mutation_testing::mutator::two_phase_borrow::MutateInReservation;
+use pcg_mutation_testing::mutator::two_phase_borrow::ReadInReservation;
+use pcg_mutation_testing::mutator::use_after_move::UseAfterMove;
 
 use pcg_mutation_testing::utils::env_feature_enabled;
 
@@ -63,6 +94,7 @@ use pcg_mutation_testing::rustc_interface::ast::Crate;
 
 use pcg::borrow_checker::r#impl::NllBorrowCheckerImpl;
 use pcg::pcg::BodyWithBorrowckFacts;
+use pcg::pcg::EvalStmtPhase;
 use pcg::run_pcg;
 use pcg::utils::CompilerCtxt;
 use pcg::PcgCtxt;
@@ -83,12 +115,15 @@ enum BorrowCheckInfo {
     NoRun,
     Passed,
     Failed { error_codes: HashSet<String> },
+    // The mutant is not well-formed MIR, so it was not borrow checked
+    InvalidMutant { reason: String },
 }
 
 // Information we record about each mutant if `MUTANTS_LOG` is set
 #[derive(Serialize)]
 struct LogEntry {
     mutation_type: String,
+    expected_outcome: ExpectedOutcome,
     borrow_check_info: BorrowCheckInfo,
     // ID of the MIR definition that this mutant was created from
     definition: String,
@@ -191,10 +226,13 @@ fn run_mutation_tests<'tcx>(
             let mutator_data = mutator_results
                 .entry(mutation.name())
                 .or_insert(MutatorData {
+                    expected: mutation.expected_outcome(),
                     instances: 0,
                     passed: 0,
                     failed: 0,
                     panicked: 0,
+                    invalid: 0,
+                    unexpected: 0,
                     error_codes: HashSet::new(),
                 });
             // let body = &body_with_borrowck_facts.body;
@@ -208,9 +246,23 @@ fn run_mutation_tests<'tcx>(
             let body_ref = &body_with_borrowck_facts.body;
 
             let ctx: CompilerCtxt<'_, '_> = CompilerCtxt::new(body_ref, tcx, borrow_checker_impl);
-            let mut mutator = Mutator::new(mutation, ctx, &mut analysis, ctx.body());
-
-            while let Some(Mutant { body, range, info }) = mutator.next() {
+            mutation.prepare(body_with_borrowck_facts);
+            let mut mutator =
+                Mutator::new(mutation, ctx, &mut analysis, ctx.body(), mutation_phases());
+
+            loop {
+                // A `Mutation` may panic while generating a mutant, e.g. on MIR it does not
+                // expect. In this case count the panic and move on to the next `Mutation`
+                let maybe_mutant =
+                    std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| mutator.next()));
+                let Mutant { body, range, info } = match maybe_mutant {
+                    Ok(Some(mutant)) => mutant,
+                    Ok(None) => break,
+                    Err(_) => {
+                        mutator_data.panicked += 1;
+                        break;
+                    }
+                };
                 info!(
                     "{}Mutation {} generated mutant at {:?}",
                     cargo_crate_name().map_or("".to_string(), |name| format!("{name}: ")),
@@ -223,7 +275,12 @@ fn run_mutation_tests<'tcx>(
                 // It is possible that the compiler raises an ICE when borrow checking a mutant.
                 // In this case catch the unwind and do not count it as `Passed` or `Failed`
                 let maybe_panic = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-                    let borrow_check_info = if do_borrowck {
+                    // Ill-formed mutants are bugs in their `Mutation`, not borrow check
+                    // failures, so they are not passed to the borrow checker
+                    let borrow_check_info = if let Err(reason) = validate_mutant(tcx, &body) {
+                        mutator_data.invalid += 1;
+                        BorrowCheckInfo::InvalidMutant { reason }
+                    } else if do_borrowck {
                         track_body_error_codes(def_id);
 
                         let (borrowck_result, mutant_body_with_borrowck_facts) = {
@@ -234,6 +291,9 @@ fn run_mutation_tests<'tcx>(
                         };
                         if let Some(_) = borrowck_result.tainted_by_errors {
                             mutator_data.failed += 1;
+                            if mutator_data.expected == ExpectedOutcome::Pass {
+                                mutator_data.unexpected += 1;
+                            }
                             let error_codes = get_registered_errors();
                             for error_code in error_codes.iter() {
                                 mutator_data.error_codes.insert(error_code.to_string());
@@ -246,6 +306,9 @@ fn run_mutation_tests<'tcx>(
                             }
                         } else {
                             mutator_data.passed += 1;
+                            if mutator_data.expected == ExpectedOutcome::Fail {
+                                mutator_data.unexpected += 1;
+                            }
                             if env_feature_enabled("PCG_VISUALIZATION").unwrap_or(false) {
                                 // Because we have forked `rustc_borrowck`, there are two identical
                                 // definitions of `BodyWithBorrowckFacts`. Here we convert from the
@@ -265,6 +328,7 @@ fn run_mutation_tests<'tcx>(
                     };
                     let log_entry = LogEntry {
                         mutation_type: mutator.name(),
+                        expected_outcome: mutation.expected_outcome(),
                         borrow_check_info,
                         definition: format!("{def_id:?}"),
                         range,
@@ -410,6 +474,40 @@ fn in_cargo_crate() -> bool {
     std::env::var("CARGO_CRATE_NAME").is_ok()
 }
 
+// The PCG phases that mutations inspect. `MUTATION_PHASES` can override the default
+// with two comma-separated phases, e.g. `PreOperands,PostMain`. If
+// `MUTATION_WITHIN_STATEMENT` is set, both phases are taken from the same statement.
+fn mutation_phases() -> PhasePair {
+    let within_statement = env_feature_enabled("MUTATION_WITHIN_STATEMENT").unwrap_or(false);
+    match std::env::var("MUTATION_PHASES") {
+        Ok(phases) => {
+            let phases: Vec<EvalStmtPhase> = phases.split(',').map(parse_eval_stmt_phase).collect();
+            match phases.as_slice() {
+                [curr, next] => PhasePair {
+                    curr: *curr,
+                    next: *next,
+                    within_statement,
+                },
+                _ => panic!("MUTATION_PHASES should contain exactly two phases, got {:?}", phases),
+            }
+        }
+        Err(_) if within_statement => {
+            PhasePair::within_statement(EvalStmtPhase::PreOperands, EvalStmtPhase::PostOperands)
+        }
+        Err(_) => PhasePair::default(),
+    }
+}
+
+fn parse_eval_stmt_phase(phase: &str) -> EvalStmtPhase {
+    match phase.trim() {
+        "PreOperands" => EvalStmtPhase::PreOperands,
+        "PostOperands" => EvalStmtPhase::PostOperands,
+        "PreMain" => EvalStmtPhase::PreMain,
+        "PostMain" => EvalStmtPhase::PostMain,
+        other => panic!("Unexpected PCG phase: '{}'. Expected one of: PreOperands, PostOperands, PreMain, PostMain", other),
+    }
+}
+
 fn init_tracing() {
     tracing_subscriber::fmt()
         .with_max_level(tracing::Level::INFO)
@@ -450,6 +548,33 @@ fn main() {
             Box::new(ReadFromWriteOnly),
             Box::new(WriteToShared),
             Box::new(MoveFromBorrowed),
+            Box::new(UseAfterMove),
+            Box::new(AssignToBorrowed),
+            Box::new(ReadFromMutablyLent),
+            Box::new(DoubleMutBorrow),
+            Box::new(StorageDeadWhileBorrowed),
+            Box::new(DropWhileBorrowed),
+            Box::new(EscapeViaReturn),
+            Box::new(EscapeViaArgument),
+            Box::new(WriteThroughShared),
+            Box::new(MoveOutOfDeref),
+            Box::new(MoveOutOfIndex),
+            Box::new(PartialMoveThenUseParent),
+            Box::new(PartialMoveThenUseSibling),
+            Box::new(ReadFromReadable),
+            Box::new(WriteToExclusive),
+            Box::new(MutablyBorrowSibling),
+            Box::new(ConflictWithClosureCapture::default()),
+            Box::new(MutateInReservation),
+            Box::new(ReadInReservation),
+            Box::new(WriteToRelabelled),
+            Box::new(WriteThroughSharedArgument),
+            Box::new(WriteThroughMutArgument),
+            Box::new(ConflictWithRegionProjection),
+            Box::new(ConflictAcrossBackEdge::default()),
+            Box::new(MutablyReborrowShared),
+            Box::new(WriteThroughSharedReborrow),
+            Box::new(SwapPastRelease),
         ],
         results_dir,
     };
@@ -0,0 +1,104 @@
+use super::utils::bogus_operand;
+use super::utils::borrowed_places;
+use super::utils::has_named_local;
+use super::utils::is_mut;
+
+use std::collections::HashSet;
+
+use super::mutator_impl::Mutant;
+use super::mutator_impl::MutantBuilder;
+use super::mutator_impl::MutantIterator;
+use super::mutator_impl::MutantStream;
+use super::mutator_impl::Mutation;
+use super::mutator_impl::PhasePair;
+
+use crate::rustc_interface::middle::mir::Body;
+use crate::rustc_interface::middle::mir::PlaceRef;
+use crate::rustc_interface::middle::mir::Rvalue;
+use crate::rustc_interface::middle::mir::StatementKind;
+
+use pcg::free_pcs::PcgLocation;
+use pcg::utils::CompilerCtxt;
+use pcg::utils::Place;
+
+// `AssignToBorrowed` creates mutants which overwrite a place behind a mutable borrow
+pub struct AssignToBorrowed;
+
+struct Iter<'a, 'tcx: 'a> {
+    borrowed: Vec<Place<'tcx>>,
+    ctx: CompilerCtxt<'a, 'tcx>,
+    body: &'a Body<'tcx>,
+    curr: PcgLocation<'tcx>,
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> MutantIterator<'a, 'mir, 'tcx> for Iter<'a, 'tcx> {
+    fn next(&mut self) -> Option<Mutant<'tcx>> {
+        while !self.borrowed.is_empty() {
+            if let Some(mutant) = self.maybe_next() {
+                return Some(mutant);
+            }
+        }
+        return None;
+    }
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> Iter<'a, 'tcx> {
+    fn maybe_next(&mut self) -> Option<Mutant<'tcx>> {
+        let place = self.borrowed.pop()?;
+        let mut builder = MutantBuilder::new(self.ctx.tcx(), self.body);
+        let lent_place = PlaceRef::from(place).to_place(self.ctx.tcx());
+
+        let new_value = bogus_operand(self.ctx.tcx(), self.body, lent_place)?;
+
+        // Overwrite `lent_place` without moving it, between `curr` and `next`
+        builder.insert_after(
+            self.curr.location,
+            StatementKind::Assign(Box::new((lent_place, Rvalue::Use(new_value)))),
+        )?;
+
+        builder.build(format!("{:?} was lent", lent_place))
+    }
+}
+
+impl Mutation for AssignToBorrowed {
+    fn make_stream<'a, 'mir: 'a, 'tcx: 'mir>(
+        &self,
+        ctx: CompilerCtxt<'a, 'tcx>,
+        body: &'a Body<'tcx>,
+        curr: PcgLocation<'tcx>,
+        next: PcgLocation<'tcx>,
+        phases: PhasePair,
+    ) -> MutantStream<'a, 'mir, 'tcx> {
+        let lent_in_curr = {
+            let borrows_graph = curr.states[phases.curr].borrow_pcg().graph();
+            borrowed_places(borrows_graph, is_mut)
+                .map(|(place, _)| place)
+                .collect::<HashSet<_>>()
+        };
+
+        let lent_in_next = {
+            let borrows_graph = next.states[phases.next]
+                .borrow_pcg()
+                .graph();
+            borrowed_places(borrows_graph, is_mut)
+                .map(|(place, _)| place)
+                .collect::<HashSet<_>>()
+        };
+        let borrowed = lent_in_curr
+            .iter()
+            .filter(|place| lent_in_next.contains(place))
+            .filter(|place| has_named_local(**place, body))
+            .map(|place| *place)
+            .collect();
+
+        MutantStream::new(Box::new(Iter {
+            borrowed,
+            ctx,
+            body,
+            curr,
+        }))
+    }
+    fn name(&self) -> String {
+        "assign-to-borrowed".into()
+    }
+}
@@ -0,0 +1,303 @@
+use super::utils::borrowed_places;
+use super::utils::has_named_local;
+use super::utils::is_assignable;
+use super::utils::BodyCache;
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use super::mutator_impl::Mutant;
+use super::mutator_impl::MutantBuilder;
+use super::mutator_impl::MutantIterator;
+use super::mutator_impl::MutantStream;
+use super::mutator_impl::Mutation;
+use super::mutator_impl::PhasePair;
+
+use crate::rustc_interface::middle::mir::visit::MutatingUseContext;
+use crate::rustc_interface::middle::mir::visit::PlaceContext;
+use crate::rustc_interface::middle::mir::visit::Visitor;
+use crate::rustc_interface::middle::mir::AggregateKind;
+use crate::rustc_interface::middle::mir::BasicBlock;
+use crate::rustc_interface::middle::mir::Body;
+use crate::rustc_interface::middle::mir::BorrowKind;
+use crate::rustc_interface::middle::mir::Local;
+use crate::rustc_interface::middle::mir::Location;
+use crate::rustc_interface::middle::mir::MutBorrowKind;
+use crate::rustc_interface::middle::mir::Place as MirPlace;
+use crate::rustc_interface::middle::mir::PlaceRef;
+use crate::rustc_interface::middle::mir::Rvalue;
+use crate::rustc_interface::middle::mir::StatementKind;
+
+use pcg::free_pcs::PcgLocation;
+use pcg::utils::CompilerCtxt;
+
+// A borrow that is captured by a closure. It only stores locations, so that it can
+// be cached across the sites of a body.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct CapturedBorrow {
+    closure: Local,
+    // Location of the statement that creates the closure
+    location: Location,
+    // Location of the statement that creates the captured borrow
+    borrow: Location,
+    // Location of the last use of the closure, see `last_use`
+    last_use: Option<Location>,
+}
+
+// A place captured by reference by a closure
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct Capture<'tcx> {
+    closure: Local,
+    place: MirPlace<'tcx>,
+    kind: BorrowKind,
+    last_use: Location,
+}
+
+impl CapturedBorrow {
+    fn capture<'tcx>(&self, body: &Body<'tcx>) -> Option<Capture<'tcx>> {
+        let statement = body.basic_blocks[self.borrow.block]
+            .statements
+            .get(self.borrow.statement_index)?;
+        match &statement.kind {
+            StatementKind::Assign(assign) => match assign.1 {
+                Rvalue::Ref(_, kind, place) => Some(Capture {
+                    closure: self.closure,
+                    place,
+                    kind,
+                    last_use: self.last_use?,
+                }),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+// Returns every borrow captured by a closure in `body`. Closures capture by
+// reference by borrowing into a temporary that is then moved into the closure
+// aggregate.
+fn closure_captures<'tcx>(body: &Body<'tcx>) -> Vec<CapturedBorrow> {
+    let mut borrows: HashMap<Local, Location> = HashMap::new();
+    let mut closures: Vec<(Local, Location, Vec<Local>)> = vec![];
+
+    for (block, bb) in body.basic_blocks.iter_enumerated() {
+        for (statement_index, statement) in bb.statements.iter().enumerate() {
+            let location = Location {
+                block,
+                statement_index,
+            };
+            if let StatementKind::Assign(assign) = &statement.kind
+                && let Some(target) = assign.0.as_local()
+            {
+                match &assign.1 {
+                    Rvalue::Ref(..) => {
+                        borrows.insert(target, location);
+                    }
+                    Rvalue::Aggregate(aggregate_kind, operands)
+                        if matches!(**aggregate_kind, AggregateKind::Closure(..)) =>
+                    {
+                        let captured_locals = operands
+                            .iter()
+                            .flat_map(|operand| operand.place()?.as_local())
+                            .collect();
+                        closures.push((target, location, captured_locals));
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    closures
+        .into_iter()
+        .flat_map(|(closure, location, captured_locals)| {
+            let last_use = last_use(body, closure, location);
+            captured_locals
+                .into_iter()
+                .flat_map(|local| borrows.get(&local))
+                .map(move |borrow| CapturedBorrow {
+                    closure,
+                    location,
+                    borrow: *borrow,
+                    last_use,
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+// Collects the locations at which a local is used, other than storage markers and
+// drops
+struct UseCollector {
+    local: Local,
+    uses: Vec<Location>,
+}
+
+impl<'tcx> Visitor<'tcx> for UseCollector {
+    fn visit_local(&mut self, local: Local, context: PlaceContext, location: Location) {
+        if local == self.local
+            && context.is_use()
+            && !context.is_storage_marker()
+            && context != PlaceContext::MutatingUse(MutatingUseContext::Drop)
+        {
+            self.uses.push(location);
+        }
+    }
+}
+
+// Returns the location of the last use of `closure` after it is created at
+// `capture_location`, ordering blocks by reverse postorder. This is usually
+// where the closure is called.
+fn last_use<'tcx>(
+    body: &Body<'tcx>,
+    closure: Local,
+    capture_location: Location,
+) -> Option<Location> {
+    let mut collector = UseCollector {
+        local: closure,
+        uses: vec![],
+    };
+    collector.visit_body(body);
+
+    let rpo_index: HashMap<BasicBlock, usize> = body
+        .basic_blocks
+        .reverse_postorder()
+        .iter()
+        .enumerate()
+        .map(|(index, block)| (*block, index))
+        .collect();
+    collector
+        .uses
+        .into_iter()
+        .filter(|location| *location != capture_location)
+        .filter(|location| capture_location.dominates(*location, body.basic_blocks.dominators()))
+        .max_by_key(|location| (rpo_index.get(&location.block), location.statement_index))
+}
+
+// `ConflictWithClosureCapture` creates mutants which access a place captured by
+// reference by a closure in a way that conflicts with the capture, while the
+// closure can still be called
+#[derive(Default)]
+pub struct ConflictWithClosureCapture {
+    captures: BodyCache<Vec<CapturedBorrow>>,
+}
+
+struct Iter<'a, 'tcx: 'a> {
+    captures: Vec<Capture<'tcx>>,
+    ctx: CompilerCtxt<'a, 'tcx>,
+    body: &'a Body<'tcx>,
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> MutantIterator<'a, 'mir, 'tcx> for Iter<'a, 'tcx> {
+    fn next(&mut self) -> Option<Mutant<'tcx>> {
+        while !self.captures.is_empty() {
+            if let Some(mutant) = self.maybe_next() {
+                return Some(mutant);
+            }
+        }
+        return None;
+    }
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> Iter<'a, 'tcx> {
+    fn maybe_next(&mut self) -> Option<Mutant<'tcx>> {
+        let capture = self.captures.pop()?;
+        let mut builder = MutantBuilder::new(self.ctx.tcx(), self.body);
+
+        // A mutable capture conflicts with reads and a shared capture with mutable borrows
+        let access = match capture.kind {
+            BorrowKind::Mut { .. } => builder.fresh_read_local(capture.place).1,
+            _ => {
+                let default_mut_borrow = BorrowKind::Mut {
+                    kind: MutBorrowKind::Default,
+                };
+                builder.fresh_ref_local(capture.place, default_mut_borrow).1
+            }
+        };
+
+        // Insert `access` right before the last use of the closure, so that the
+        // captured borrow is still live
+        builder.insert_before(capture.last_use, access)?;
+
+        builder.build(format!(
+            "{:?} was captured by {:?} in {:?}",
+            capture.place, capture.kind, capture.closure
+        ))
+    }
+}
+
+impl Mutation for ConflictWithClosureCapture {
+    fn make_stream<'a, 'mir: 'a, 'tcx: 'mir>(
+        &self,
+        ctx: CompilerCtxt<'a, 'tcx>,
+        body: &'a Body<'tcx>,
+        curr: PcgLocation<'tcx>,
+        next: PcgLocation<'tcx>,
+        phases: PhasePair,
+    ) -> MutantStream<'a, 'mir, 'tcx> {
+        let tcx = ctx.tcx();
+
+        let lent_in_next = {
+            let borrows_graph = next.states[phases.next]
+                .borrow_pcg()
+                .graph();
+            borrowed_places(borrows_graph, |_| true)
+                .filter(|(place, _)| has_named_local(*place, body))
+                .map(|(place, _)| PlaceRef::from(*place).to_place(tcx))
+                .collect::<HashSet<_>>()
+        };
+
+        // Closures that still exist at both `curr` and `next`, so they can be called later
+        let locals_in_next: HashSet<Local> = next.states[phases.next]
+            .capabilities()
+            .iter()
+            .map(|(place, _)| place.local)
+            .collect();
+        let live_closures: HashSet<Local> = curr.states[phases.curr]
+            .capabilities()
+            .iter()
+            .map(|(place, _)| place.local)
+            .filter(|local| locals_in_next.contains(local))
+            .collect();
+
+        // Only consider captures created by `curr` whose borrow is live in the
+        // borrows graph. Sites on CFG edges would create the same mutants again. A
+        // shared capture is answered with a mutable borrow, so its place must be
+        // assignable.
+        let created_by_curr: Vec<CapturedBorrow> = self.captures.with_mut(
+            body,
+            || closure_captures(body),
+            |captures| {
+                captures
+                    .iter()
+                    .filter(|captured| {
+                        captured.location == curr.location
+                            && next.location.block == curr.location.block
+                    })
+                    .copied()
+                    .collect()
+            },
+        );
+        let captures = created_by_curr
+            .into_iter()
+            .filter(|captured| live_closures.contains(&captured.closure))
+            .flat_map(|captured| captured.capture(body))
+            .filter(|capture| lent_in_next.contains(&capture.place))
+            .filter(|capture| {
+                matches!(capture.kind, BorrowKind::Mut { .. })
+                    || is_assignable(tcx, body, capture.place)
+            })
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+
+        MutantStream::new(Box::new(Iter {
+            captures,
+            ctx,
+            body,
+        }))
+    }
+    fn name(&self) -> String {
+        "conflict-with-closure-capture".into()
+    }
+}
@@ -0,0 +1,117 @@
+use super::utils::borrowed_places;
+use super::utils::has_named_local;
+use super::utils::is_mut;
+
+use std::collections::HashSet;
+
+use super::mutator_impl::Mutant;
+use super::mutator_impl::MutantBuilder;
+use super::mutator_impl::MutantIterator;
+use super::mutator_impl::MutantStream;
+use super::mutator_impl::Mutation;
+use super::mutator_impl::PhasePair;
+
+use crate::rustc_interface::middle::mir::Body;
+use crate::rustc_interface::middle::mir::BorrowKind;
+use crate::rustc_interface::middle::mir::FakeReadCause;
+use crate::rustc_interface::middle::mir::MutBorrowKind;
+use crate::rustc_interface::middle::mir::Place as MirPlace;
+use crate::rustc_interface::middle::mir::PlaceRef;
+use crate::rustc_interface::middle::mir::StatementKind;
+
+use pcg::free_pcs::PcgLocation;
+use pcg::utils::CompilerCtxt;
+use pcg::utils::Place;
+
+// `DoubleMutBorrow` creates mutants which mutably borrow a place that is
+// already behind a mutable borrow
+pub struct DoubleMutBorrow;
+
+struct Iter<'a, 'tcx: 'a> {
+    mutably_lent: Vec<Place<'tcx>>,
+    ctx: CompilerCtxt<'a, 'tcx>,
+    body: &'a Body<'tcx>,
+    curr: PcgLocation<'tcx>,
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> MutantIterator<'a, 'mir, 'tcx> for Iter<'a, 'tcx> {
+    fn next(&mut self) -> Option<Mutant<'tcx>> {
+        while !self.mutably_lent.is_empty() {
+            if let Some(mutant) = self.maybe_next() {
+                return Some(mutant);
+            }
+        }
+        return None;
+    }
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> Iter<'a, 'tcx> {
+    fn maybe_next(&mut self) -> Option<Mutant<'tcx>> {
+        let place = self.mutably_lent.pop()?;
+        let lent_place = PlaceRef::from(place).to_place(self.ctx.tcx());
+        let mut builder = MutantBuilder::new(self.ctx.tcx(), self.body);
+
+        let default_mut_borrow = BorrowKind::Mut {
+            kind: MutBorrowKind::Default,
+        };
+
+        // Mutably borrow `lent_place` into a `fresh_local` between `curr` and `next`
+        let (fresh_local, new_borrow) = builder.fresh_ref_local(lent_place, default_mut_borrow);
+        let borrow_location = builder.insert_after(self.curr.location, new_borrow)?;
+
+        // Use `fresh_local` so that the new borrow is live
+        builder.insert_after(
+            borrow_location,
+            StatementKind::FakeRead(Box::new((
+                FakeReadCause::ForLet(None),
+                MirPlace::from(fresh_local),
+            ))),
+        )?;
+
+        builder.build(format!("{:?} was mutably lent", lent_place))
+    }
+}
+
+impl Mutation for DoubleMutBorrow {
+    fn make_stream<'a, 'mir: 'a, 'tcx: 'mir>(
+        &self,
+        ctx: CompilerCtxt<'a, 'tcx>,
+        body: &'a Body<'tcx>,
+        curr: PcgLocation<'tcx>,
+        next: PcgLocation<'tcx>,
+        phases: PhasePair,
+    ) -> MutantStream<'a, 'mir, 'tcx> {
+        let mutably_lent_in_curr = {
+            let borrows_graph = curr.states[phases.curr].borrow_pcg().graph();
+            borrowed_places(borrows_graph, is_mut)
+        };
+
+        let mutably_lent_in_next = {
+            let borrows_graph = next.states[phases.next]
+                .borrow_pcg()
+                .graph();
+            borrowed_places(borrows_graph, is_mut)
+                .map(|(place, _)| place)
+                .collect::<HashSet<_>>()
+        };
+
+        // A place lent through several regions is only borrowed once
+        let mutably_lent = mutably_lent_in_curr
+            .filter(|(place, _)| mutably_lent_in_next.contains(place))
+            .filter(|(place, _)| has_named_local(*place, body))
+            .map(|(place, _)| place)
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+
+        MutantStream::new(Box::new(Iter {
+            mutably_lent,
+            ctx,
+            body,
+            curr,
+        }))
+    }
+    fn name(&self) -> String {
+        "double-mut-borrow".into()
+    }
+}
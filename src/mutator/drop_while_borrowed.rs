@@ -0,0 +1,114 @@
+use super::utils::borrowed_places_with_blockers;
+use super::utils::has_named_local;
+
+use std::collections::HashSet;
+
+use super::mutator_impl::Mutant;
+use super::mutator_impl::MutantBuilder;
+use super::mutator_impl::MutantIterator;
+use super::mutator_impl::MutantStream;
+use super::mutator_impl::Mutation;
+use super::mutator_impl::PhasePair;
+
+use crate::rustc_interface::middle::mir::Body;
+use crate::rustc_interface::middle::mir::FakeReadCause;
+use crate::rustc_interface::middle::mir::PlaceRef;
+use crate::rustc_interface::middle::mir::StatementKind;
+use crate::rustc_interface::middle::mir::TerminatorKind;
+use crate::rustc_interface::middle::mir::UnwindAction;
+
+use pcg::free_pcs::PcgLocation;
+use pcg::utils::CompilerCtxt;
+use pcg::utils::Place;
+
+// `DropWhileBorrowed` creates mutants which drop a borrowed place and then
+// use the blocking reference
+pub struct DropWhileBorrowed;
+
+struct Iter<'a, 'tcx: 'a> {
+    borrowed: Vec<(Place<'tcx>, Place<'tcx>)>,
+    ctx: CompilerCtxt<'a, 'tcx>,
+    body: &'a Body<'tcx>,
+    curr: PcgLocation<'tcx>,
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> MutantIterator<'a, 'mir, 'tcx> for Iter<'a, 'tcx> {
+    fn next(&mut self) -> Option<Mutant<'tcx>> {
+        while !self.borrowed.is_empty() {
+            if let Some(mutant) = self.maybe_next() {
+                return Some(mutant);
+            }
+        }
+        return None;
+    }
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> Iter<'a, 'tcx> {
+    fn maybe_next(&mut self) -> Option<Mutant<'tcx>> {
+        let (place, blocking_place) = self.borrowed.pop()?;
+        let lent_place = PlaceRef::from(*place).to_place(self.ctx.tcx());
+        let blocking_place = PlaceRef::from(*blocking_place).to_place(self.ctx.tcx());
+        let mut builder = MutantBuilder::new(self.ctx.tcx(), self.body);
+
+        // `use_bb` is reached after the drop and uses `blocking_place`
+        let use_bb_index = builder.fresh_block(TerminatorKind::Unreachable);
+
+        // Drop `lent_place` on an imaginary branch which continues to `use_bb`
+        builder.split_with_imaginary_branch(
+            self.curr.location,
+            vec![],
+            TerminatorKind::Drop {
+                place: lent_place,
+                target: use_bb_index,
+                unwind: UnwindAction::Unreachable,
+                replace: false,
+            },
+        )?;
+        builder.push_statement(
+            use_bb_index,
+            StatementKind::FakeRead(Box::new((FakeReadCause::ForLet(None), blocking_place))),
+        );
+
+        builder.build(format!("{:?} was borrowed by {:?}", lent_place, blocking_place))
+    }
+}
+
+impl Mutation for DropWhileBorrowed {
+    fn make_stream<'a, 'mir: 'a, 'tcx: 'mir>(
+        &self,
+        ctx: CompilerCtxt<'a, 'tcx>,
+        body: &'a Body<'tcx>,
+        curr: PcgLocation<'tcx>,
+        next: PcgLocation<'tcx>,
+        phases: PhasePair,
+    ) -> MutantStream<'a, 'mir, 'tcx> {
+        let borrows_graph = next.states[phases.next]
+            .borrow_pcg()
+            .graph();
+
+        // Dropping a place whose type has no drop glue does not conflict with
+        // borrows of it, so we skip those
+        let borrowed: HashSet<(Place<'tcx>, Place<'tcx>)> =
+            borrowed_places_with_blockers(borrows_graph, ctx, |_| true)
+                .into_iter()
+                .filter(|(place, _)| has_named_local(*place, body))
+                .filter(|(place, blocking_place)| place.local != blocking_place.local)
+                .filter(|(place, _)| {
+                    place
+                        .ty(ctx)
+                        .ty
+                        .needs_drop(ctx.tcx(), body.typing_env(ctx.tcx()))
+                })
+                .collect();
+
+        MutantStream::new(Box::new(Iter {
+            borrowed: borrowed.into_iter().collect(),
+            ctx,
+            body,
+            curr,
+        }))
+    }
+    fn name(&self) -> String {
+        "drop-while-borrowed".into()
+    }
+}
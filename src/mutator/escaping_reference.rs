@@ -0,0 +1,376 @@
+use super::utils::has_named_local;
+use super::utils::remote_places;
+
+use std::collections::HashSet;
+
+use super::mutator_impl::Mutant;
+use super::mutator_impl::MutantBuilder;
+use super::mutator_impl::MutantIterator;
+use super::mutator_impl::MutantStream;
+use super::mutator_impl::Mutation;
+use super::mutator_impl::PhasePair;
+
+use crate::rustc_interface::middle::mir::Body;
+use crate::rustc_interface::middle::mir::BorrowKind;
+use crate::rustc_interface::middle::mir::Local;
+use crate::rustc_interface::middle::mir::Location;
+use crate::rustc_interface::middle::mir::Mutability;
+use crate::rustc_interface::middle::mir::MutBorrowKind;
+use crate::rustc_interface::middle::mir::Operand;
+use crate::rustc_interface::middle::mir::Place as MirPlace;
+use crate::rustc_interface::middle::mir::PlaceRef;
+use crate::rustc_interface::middle::mir::ProjectionElem;
+use crate::rustc_interface::middle::mir::Rvalue;
+use crate::rustc_interface::middle::mir::StatementKind;
+use crate::rustc_interface::middle::mir::TerminatorKind;
+use crate::rustc_interface::middle::mir::RETURN_PLACE;
+use crate::rustc_interface::middle::ty::Region;
+use crate::rustc_interface::middle::ty::Ty;
+use crate::rustc_interface::middle::ty::TyCtxt;
+use crate::rustc_interface::middle::ty::TyKind;
+
+use pcg::free_pcs::PcgLocation;
+use pcg::utils::CompilerCtxt;
+use pcg::utils::Place;
+
+// A place outside of the function body that a reference can escape into
+#[derive(Clone, Copy)]
+struct EscapeTarget<'tcx> {
+    place: MirPlace<'tcx>,
+    // Mutability of the reference stored in `place`
+    mutability: Mutability,
+    // Type of the data the reference stored in `place` points to
+    pointee_ty: Ty<'tcx>,
+    // Region of the reference stored in `place`, as written in the signature
+    sig_region: Region<'tcx>,
+}
+
+// A place that we borrow and write into an `EscapeTarget`. `sig_region` is the
+// region in the signature of the argument the place is reached from, or `None`
+// if the place is owned by the function.
+#[derive(Clone, Copy)]
+struct EscapeSource<'tcx> {
+    place: Place<'tcx>,
+    sig_region: Option<Region<'tcx>>,
+}
+
+// Returns the type of `local` in the signature of the function defining `body`.
+// `local` must be the return place or an argument.
+fn sig_ty<'tcx>(tcx: TyCtxt<'tcx>, body: &Body<'tcx>, local: Local) -> Ty<'tcx> {
+    let fn_sig = tcx
+        .fn_sig(body.source.def_id())
+        .instantiate_identity()
+        .skip_binder();
+    if local == RETURN_PLACE {
+        fn_sig.output()
+    } else {
+        fn_sig.inputs()[local.index() - 1]
+    }
+}
+
+fn ref_region<'tcx>(ty: Ty<'tcx>) -> Option<Region<'tcx>> {
+    match ty.kind() {
+        TyKind::Ref(region, _, _) => Some(*region),
+        _ => None,
+    }
+}
+
+// Returns true if `sup` outlives `sub` according to the signature of the function
+// defining `body`: they are the same region, `sup` is `'static`, or it follows from
+// the explicit outlives bounds. Bounds implied by the argument types, e.g. `'b: 'a`
+// for `&'a &'b T`, are not taken into account.
+fn sig_outlives<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+    sup: Region<'tcx>,
+    sub: Region<'tcx>,
+) -> bool {
+    let bounds: Vec<_> = tcx
+        .param_env(body.source.def_id())
+        .caller_bounds()
+        .iter()
+        .filter_map(|clause| clause.as_region_outlives_clause())
+        .map(|outlives| outlives.skip_binder())
+        .map(|outlives| (outlives.0, outlives.1))
+        .collect();
+
+    let mut outlived = vec![sup];
+    let mut worklist = vec![sup];
+    while let Some(region) = worklist.pop() {
+        if region == sub || region.is_static() {
+            return true;
+        }
+        for (longer, shorter) in bounds.iter() {
+            if *longer == region && !outlived.contains(shorter) {
+                outlived.push(*shorter);
+                worklist.push(*shorter);
+            }
+        }
+    }
+    false
+}
+
+// Returns the return place if the function returns a reference
+fn return_targets<'tcx>(tcx: TyCtxt<'tcx>, body: &Body<'tcx>) -> Vec<EscapeTarget<'tcx>> {
+    let return_ty = body.local_decls[RETURN_PLACE].ty;
+    match (return_ty.kind(), ref_region(sig_ty(tcx, body, RETURN_PLACE))) {
+        (TyKind::Ref(_, pointee_ty, mutability), Some(sig_region)) => vec![EscapeTarget {
+            place: MirPlace::from(RETURN_PLACE),
+            mutability: *mutability,
+            pointee_ty: *pointee_ty,
+            sig_region,
+        }],
+        _ => vec![],
+    }
+}
+
+// Returns `*arg` for each argument `arg` of type `&mut &T` or `&mut &mut T`
+fn argument_targets<'tcx>(tcx: TyCtxt<'tcx>, body: &Body<'tcx>) -> Vec<EscapeTarget<'tcx>> {
+    body.args_iter()
+        .flat_map(|arg| match body.local_decls[arg].ty.kind() {
+            TyKind::Ref(_, inner_ty, Mutability::Mut) => match inner_ty.kind() {
+                TyKind::Ref(_, pointee_ty, mutability) => {
+                    let sig_region = match sig_ty(tcx, body, arg).kind() {
+                        TyKind::Ref(_, sig_inner_ty, _) => ref_region(*sig_inner_ty),
+                        _ => None,
+                    }?;
+                    Some(EscapeTarget {
+                        place: tcx.mk_place_deref(MirPlace::from(arg)),
+                        mutability: *mutability,
+                        pointee_ty: *pointee_ty,
+                        sig_region,
+                    })
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+// Returns true if `local` may be written to by a statement or call after `location`.
+// Such a write would overwrite a reference escaping into `local` at `location`, so
+// that it no longer escapes.
+fn written_after<'tcx>(body: &Body<'tcx>, local: Local, location: Location) -> bool {
+    let writes = |kind: &StatementKind<'tcx>| match kind {
+        StatementKind::Assign(assign) => assign.0.local == local,
+        _ => false,
+    };
+    let call_writes = |kind: &TerminatorKind<'tcx>| match kind {
+        TerminatorKind::Call { destination, .. } => destination.local == local,
+        _ => false,
+    };
+
+    let bb = &body.basic_blocks[location.block];
+    let rest_writes = bb
+        .statements
+        .iter()
+        .skip(location.statement_index + 1)
+        .any(|statement| writes(&statement.kind));
+    // A site at the terminator is on the edge after it
+    let terminator_writes =
+        location.statement_index < bb.statements.len() && call_writes(&bb.terminator().kind);
+    if rest_writes || terminator_writes {
+        return true;
+    }
+
+    let mut visited = HashSet::new();
+    let mut worklist: Vec<_> = bb.terminator().successors().collect();
+    while let Some(block) = worklist.pop() {
+        if !visited.insert(block) {
+            continue;
+        }
+        let bb = &body.basic_blocks[block];
+        if bb.statements.iter().any(|statement| writes(&statement.kind))
+            || call_writes(&bb.terminator().kind)
+        {
+            return true;
+        }
+        worklist.extend(bb.terminator().successors());
+    }
+    false
+}
+
+// Returns the places owned by the function at both `curr` and `next`
+fn local_sources<'tcx>(
+    body: &Body<'tcx>,
+    curr: &PcgLocation<'tcx>,
+    next: &PcgLocation<'tcx>,
+    phases: PhasePair,
+) -> Vec<EscapeSource<'tcx>> {
+    let owned_in_next: HashSet<_> = next.states[phases.next]
+        .capabilities()
+        .iter()
+        .map(|(place, _)| place)
+        .collect();
+
+    curr.states[phases.curr]
+        .capabilities()
+        .iter()
+        .map(|(place, _)| place)
+        .filter(|place| owned_in_next.contains(place))
+        .filter(|place| has_named_local(*place, body))
+        .filter(|place| !place.projection.contains(&ProjectionElem::Deref))
+        .map(|place| EscapeSource {
+            place,
+            sig_region: None,
+        })
+        .collect()
+}
+
+// Returns the places provided by arguments which are in the borrows graph at `next`
+fn remote_sources<'tcx>(
+    ctx: CompilerCtxt<'_, 'tcx>,
+    body: &Body<'tcx>,
+    next: &PcgLocation<'tcx>,
+    phases: PhasePair,
+) -> Vec<EscapeSource<'tcx>> {
+    let borrows_graph = next.states[phases.next]
+        .borrow_pcg()
+        .graph();
+    remote_places(borrows_graph, ctx)
+        .into_iter()
+        .map(|place| EscapeSource {
+            place,
+            sig_region: ref_region(sig_ty(ctx.tcx(), body, place.local)),
+        })
+        .collect()
+}
+
+struct Iter<'a, 'tcx: 'a> {
+    escapes: Vec<(EscapeTarget<'tcx>, EscapeSource<'tcx>)>,
+    ctx: CompilerCtxt<'a, 'tcx>,
+    body: &'a Body<'tcx>,
+    curr: PcgLocation<'tcx>,
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> MutantIterator<'a, 'mir, 'tcx> for Iter<'a, 'tcx> {
+    fn next(&mut self) -> Option<Mutant<'tcx>> {
+        while !self.escapes.is_empty() {
+            if let Some(mutant) = self.maybe_next() {
+                return Some(mutant);
+            }
+        }
+        return None;
+    }
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> Iter<'a, 'tcx> {
+    fn maybe_next(&mut self) -> Option<Mutant<'tcx>> {
+        let (target, source) = self.escapes.pop()?;
+        let source_place = PlaceRef::from(*source.place).to_place(self.ctx.tcx());
+        let mut builder = MutantBuilder::new(self.ctx.tcx(), self.body);
+
+        let borrow_kind = match target.mutability {
+            Mutability::Mut => BorrowKind::Mut {
+                kind: MutBorrowKind::Default,
+            },
+            Mutability::Not => BorrowKind::Shared,
+        };
+
+        // Borrow `source_place` into a fresh local between `curr` and `next`
+        let (fresh_local, new_borrow) = builder.fresh_ref_local(source_place, borrow_kind);
+        let borrow_loc = builder.insert_after(self.curr.location, new_borrow)?;
+
+        // Let the new borrow escape into `target`
+        builder.insert_after(
+            borrow_loc,
+            StatementKind::Assign(Box::new((
+                target.place,
+                Rvalue::Use(Operand::Move(MirPlace::from(fresh_local))),
+            ))),
+        )?;
+
+        builder.build(format!("{:?} can escape into {:?}", source_place, target.place))
+    }
+}
+
+// Pairs each target that is not overwritten after `curr` with every source of the
+// type it points to. Borrows of remote places are only allowed to escape if the
+// signature says they outlive the target, so we skip those sources.
+fn escapes<'tcx>(
+    ctx: CompilerCtxt<'_, 'tcx>,
+    body: &Body<'tcx>,
+    curr: &PcgLocation<'tcx>,
+    targets: Vec<EscapeTarget<'tcx>>,
+    sources: Vec<EscapeSource<'tcx>>,
+) -> Vec<(EscapeTarget<'tcx>, EscapeSource<'tcx>)> {
+    let tcx = ctx.tcx();
+    targets
+        .iter()
+        .filter(|target| !written_after(body, target.place.local, curr.location))
+        .flat_map(|target| {
+            sources
+                .iter()
+                .filter(move |source| {
+                    let source_ty = source.place.ty(ctx).ty;
+                    tcx.erase_regions(source_ty) == tcx.erase_regions(target.pointee_ty)
+                })
+                .filter(move |source| {
+                    source.sig_region.is_none_or(|sig_region| {
+                        !sig_outlives(tcx, body, sig_region, target.sig_region)
+                    })
+                })
+                .filter(move |source| source.place.local != target.place.local)
+                .map(move |source| (*target, *source))
+        })
+        .collect()
+}
+
+// `EscapeViaReturn` creates mutants which return a reference to a place owned by
+// the function or to a place provided by an unrelated argument
+pub struct EscapeViaReturn;
+
+impl Mutation for EscapeViaReturn {
+    fn make_stream<'a, 'mir: 'a, 'tcx: 'mir>(
+        &self,
+        ctx: CompilerCtxt<'a, 'tcx>,
+        body: &'a Body<'tcx>,
+        curr: PcgLocation<'tcx>,
+        next: PcgLocation<'tcx>,
+        phases: PhasePair,
+    ) -> MutantStream<'a, 'mir, 'tcx> {
+        let targets = return_targets(ctx.tcx(), body);
+        let mut sources = local_sources(body, &curr, &next, phases);
+        sources.extend(remote_sources(ctx, body, &next, phases));
+
+        MutantStream::new(Box::new(Iter {
+            escapes: escapes(ctx, body, &curr, targets, sources),
+            ctx,
+            body,
+            curr,
+        }))
+    }
+    fn name(&self) -> String {
+        "escape-via-return".into()
+    }
+}
+
+// `EscapeViaArgument` creates mutants which write a reference to a place owned by
+// the function, or provided by another argument, into a place reachable from an argument
+pub struct EscapeViaArgument;
+
+impl Mutation for EscapeViaArgument {
+    fn make_stream<'a, 'mir: 'a, 'tcx: 'mir>(
+        &self,
+        ctx: CompilerCtxt<'a, 'tcx>,
+        body: &'a Body<'tcx>,
+        curr: PcgLocation<'tcx>,
+        next: PcgLocation<'tcx>,
+        phases: PhasePair,
+    ) -> MutantStream<'a, 'mir, 'tcx> {
+        let targets = argument_targets(ctx.tcx(), body);
+        let mut sources = local_sources(body, &curr, &next, phases);
+        sources.extend(remote_sources(ctx, body, &next, phases));
+
+        MutantStream::new(Box::new(Iter {
+            escapes: escapes(ctx, body, &curr, targets, sources),
+            ctx,
+            body,
+            curr,
+        }))
+    }
+    fn name(&self) -> String {
+        "escape-via-argument".into()
+    }
+}
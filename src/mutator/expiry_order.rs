@@ -1,7 +1,4 @@
-use super::utils::bogus_source_info;
 use super::utils::borrowed_places;
-use super::utils::fresh_basic_block;
-use super::utils::fresh_local;
 use super::utils::has_named_local;
 use super::utils::is_mut;
 use super::utils::local_node_to_current_place;
@@ -9,30 +6,22 @@ use super::utils::local_node_to_current_place;
 use std::collections::HashSet;
 
 use super::mutator_impl::Mutant;
+use super::mutator_impl::MutantBuilder;
 use super::mutator_impl::MutantIterator;
-use super::mutator_impl::MutantLocation;
-use super::mutator_impl::MutantRange;
 use super::mutator_impl::MutantStream;
 use super::mutator_impl::Mutation;
+use super::mutator_impl::PhasePair;
 
 use crate::rustc_interface::middle::mir::Body;
 use crate::rustc_interface::middle::mir::BorrowKind;
 use crate::rustc_interface::middle::mir::Mutability;
-use crate::rustc_interface::middle::mir::Place as MirPlace;
 use crate::rustc_interface::middle::mir::PlaceRef;
-use crate::rustc_interface::middle::mir::Rvalue;
-use crate::rustc_interface::middle::mir::Statement;
 use crate::rustc_interface::middle::mir::StatementKind;
-use crate::rustc_interface::middle::mir::Terminator;
 use crate::rustc_interface::middle::mir::TerminatorKind;
-use crate::rustc_interface::middle::ty::Region;
-use crate::rustc_interface::middle::ty::RegionVid;
-use crate::rustc_interface::middle::ty::Ty;
 use crate::rustc_interface::middle::ty::TyCtxt;
 
 use pcg::free_pcs::PcgLocation;
 
-use pcg::pcg::EvalStmtPhase;
 use pcg::pcg::PCGNode;
 
 use pcg::utils::place::Place;
@@ -90,23 +79,14 @@ fn places_blocking<'mir, 'tcx>(
 // into fresh local variables in the same order
 fn places_to_statements<'tcx>(
     tcx: TyCtxt<'tcx>,
-    body: &mut Body<'tcx>,
+    builder: &mut MutantBuilder<'tcx>,
     mut places: Vec<Place<'tcx>>,
-) -> Vec<Statement<'tcx>> {
+) -> Vec<StatementKind<'tcx>> {
     places
         .drain(..)
         .map(|place| {
             let mir_place = PlaceRef::from(*place).to_place(tcx);
-            let region = Region::new_var(tcx, RegionVid::MAX);
-            let target_ty = Ty::new_mut_ref(tcx, region, mir_place.ty(&body.local_decls, tcx).ty);
-            let target = fresh_local(body, target_ty);
-            Statement {
-                source_info: bogus_source_info(body),
-                kind: StatementKind::Assign(Box::new((
-                    MirPlace::from(target),
-                    Rvalue::Ref(region, BorrowKind::Shared, mir_place),
-                ))),
-            }
+            builder.fresh_ref_local(mir_place, BorrowKind::Shared).1
         })
         .collect()
 }
@@ -116,7 +96,6 @@ struct Iter<'a, 'tcx: 'a> {
     ctx: CompilerCtxt<'a, 'tcx>,
     body: &'a Body<'tcx>,
     curr: PcgLocation<'tcx>,
-    next: PcgLocation<'tcx>,
 }
 
 impl<'a, 'mir: 'a, 'tcx: 'mir> MutantIterator<'a, 'mir, 'tcx> for Iter<'a, 'tcx> {
@@ -133,80 +112,20 @@ impl<'a, 'mir: 'a, 'tcx: 'mir> MutantIterator<'a, 'mir, 'tcx> for Iter<'a, 'tcx>
 impl<'a, 'mir: 'a, 'tcx: 'mir> Iter<'a, 'tcx> {
     fn maybe_next(&mut self) -> Option<Mutant<'tcx>> {
         let (place, blocking_place) = self.mutant_sequences.pop()?;
-        let mut mutant_body = self.body.clone();
+        let mut builder = MutantBuilder::new(self.ctx.tcx(), self.body);
         let mutant_sequence = places_to_statements(
             self.ctx.tcx(),
-            &mut mutant_body,
+            &mut builder,
             vec![place, blocking_place], // (p2, p1)
         );
-        let curr_bb_index = self.curr.location.block;
-        // Split the original basic block between `curr` and `next`
-        let bb = mutant_body
-            .basic_blocks_mut()
-            .get_mut(curr_bb_index)
-            .unwrap();
-        let mut tail_statements = bb
-            .statements
-            .drain(self.next.location.statement_index..)
-            .collect();
-
-        let bb_terminator = bb.terminator.take();
-        let tail_bb_index = fresh_basic_block(&mut mutant_body);
-        let tail_bb = mutant_body
-            .basic_blocks_mut()
-            .get_mut(tail_bb_index)
-            .unwrap();
-        tail_bb.statements.append(&mut tail_statements);
-        tail_bb.terminator = bb_terminator;
-
-        let bogus_source_info = bogus_source_info(&mutant_body);
-
-        // `mutant_bb` is the branch in which we expire p2 before p1
-        let mutant_bb_index = fresh_basic_block(&mut mutant_body);
-        let mutant_bb = mutant_body
-            .basic_blocks_mut()
-            .get_mut(mutant_bb_index)
-            .unwrap();
-        mutant_bb.statements = mutant_sequence.clone();
-        mutant_bb.terminator = Some(Terminator {
-            source_info: bogus_source_info,
-            kind: TerminatorKind::Unreachable,
-        });
-
-        let start_loc = MutantLocation {
-            basic_block: mutant_bb_index.into(),
-            statement_index: 0,
-        };
-
-        let end_loc = MutantLocation {
-            basic_block: mutant_bb_index.into(),
-            statement_index: self.curr.location.statement_index,
-        };
-
-        let bb = mutant_body
-            .basic_blocks_mut()
-            .get_mut(curr_bb_index)
-            .unwrap();
-
-        // Terminator of the original basic block becomes a false branch.
-        // Control will always flow to `tail_bb` but the compiler type-checks
-        // the body as if it could go to `mutant_bb`.
-        bb.terminator = Some(Terminator {
-            source_info: bogus_source_info,
-            kind: TerminatorKind::FalseEdge {
-                real_target: tail_bb_index,
-                imaginary_target: mutant_bb_index,
-            },
-        });
-
-        Some(Mutant {
-            body: mutant_body,
-            range: MutantRange {
-                start: start_loc,
-                end: end_loc,
-            },
-            info: format!("created mutant expiry sequence {:?}", mutant_sequence).to_string(),
-        })
+        // Expire p2 before p1 on an imaginary branch
+        builder.split_with_imaginary_branch(
+            self.curr.location,
+            mutant_sequence,
+            TerminatorKind::Unreachable,
+        )?;
+
+        builder.build(format!("{:?} was blocked by {:?}", place, blocking_place))
     }
 }
 
@@ -221,10 +140,11 @@ impl Mutation for BorrowExpiryOrder {
         body: &'a Body<'tcx>,
         curr: PcgLocation<'tcx>,
         next: PcgLocation<'tcx>,
+        phases: PhasePair,
     ) -> MutantStream<'a, 'mir, 'tcx> {
         let mut mutant_sequences = vec![];
 
-        let borrows_graph = next.states[EvalStmtPhase::PostOperands]
+        let borrows_graph = next.states[phases.next]
             .borrow_pcg()
             .graph();
 
@@ -281,7 +201,6 @@ impl Mutation for BorrowExpiryOrder {
             ctx,
             body,
             curr,
-            next,
         }))
     }
     fn name(&self) -> String {
@@ -300,10 +219,11 @@ impl Mutation for AbstractExpiryOrder {
         body: &'a Body<'tcx>,
         curr: PcgLocation<'tcx>,
         next: PcgLocation<'tcx>,
+        phases: PhasePair,
     ) -> MutantStream<'a, 'mir, 'tcx> {
         let mut mutant_sequences = vec![];
 
-        let borrows_graph = next.states[EvalStmtPhase::PostOperands]
+        let borrows_graph = next.states[phases.next]
             .borrow_pcg()
             .graph();
 
@@ -362,7 +282,6 @@ impl Mutation for AbstractExpiryOrder {
             ctx,
             body,
             curr,
-            next,
         }))
     }
     fn name(&self) -> String {
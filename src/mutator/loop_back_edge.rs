@@ -0,0 +1,154 @@
+use super::utils::has_named_local;
+use super::utils::BodyCache;
+use super::utils::local_node_to_current_place;
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use super::mutator_impl::Mutant;
+use super::mutator_impl::MutantBuilder;
+use super::mutator_impl::MutantIterator;
+use super::mutator_impl::MutantStream;
+use super::mutator_impl::Mutation;
+use super::mutator_impl::PhasePair;
+
+use crate::rustc_interface::middle::mir::BasicBlock;
+use crate::rustc_interface::middle::mir::Body;
+use crate::rustc_interface::middle::mir::BorrowKind;
+use crate::rustc_interface::middle::mir::MutBorrowKind;
+use crate::rustc_interface::middle::mir::PlaceRef;
+
+use pcg::borrow_pcg::borrow_pcg_edge::BorrowPcgEdgeLike;
+use pcg::borrow_pcg::edge::abstraction::AbstractionType;
+use pcg::borrow_pcg::edge::kind::BorrowPcgEdgeKind;
+use pcg::borrow_pcg::edge_data::EdgeData;
+use pcg::free_pcs::PcgLocation;
+use pcg::utils::CompilerCtxt;
+use pcg::utils::Place;
+
+// Returns every loop head in `body`, i.e. every reachable block with an edge back
+// to it from a block it dominates, paired with the sources of those back edges
+fn loop_heads<'tcx>(body: &Body<'tcx>) -> HashMap<BasicBlock, Vec<BasicBlock>> {
+    let dominators = body.basic_blocks.dominators();
+    let mut loop_heads: HashMap<BasicBlock, Vec<BasicBlock>> = HashMap::new();
+    for (source, bb) in body.basic_blocks.iter_enumerated() {
+        if !dominators.is_reachable(source) {
+            continue;
+        }
+        for target in bb.terminator().successors() {
+            if dominators.dominates(target, source) {
+                loop_heads.entry(target).or_default().push(source);
+            }
+        }
+    }
+    loop_heads
+}
+
+struct Iter<'a, 'tcx: 'a> {
+    // Pairs of a place blocked across iterations and the end of a block with a back edge
+    carried: Vec<(Place<'tcx>, BasicBlock)>,
+    ctx: CompilerCtxt<'a, 'tcx>,
+    body: &'a Body<'tcx>,
+    loop_head: BasicBlock,
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> MutantIterator<'a, 'mir, 'tcx> for Iter<'a, 'tcx> {
+    fn next(&mut self) -> Option<Mutant<'tcx>> {
+        while !self.carried.is_empty() {
+            if let Some(mutant) = self.maybe_next() {
+                return Some(mutant);
+            }
+        }
+        return None;
+    }
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> Iter<'a, 'tcx> {
+    fn maybe_next(&mut self) -> Option<Mutant<'tcx>> {
+        let tcx = self.ctx.tcx();
+        let (place, back_edge_source) = self.carried.pop()?;
+        let carried_place = PlaceRef::from(*place).to_place(tcx);
+        let mut builder = MutantBuilder::new(tcx, self.body);
+
+        let default_mut_borrow = BorrowKind::Mut {
+            kind: MutBorrowKind::Default,
+        };
+        let (_, new_borrow) = builder.fresh_ref_local(carried_place, default_mut_borrow);
+
+        // Insert `new_borrow` right before the back edge
+        builder.push_statement(back_edge_source, new_borrow);
+
+        builder.build(format!(
+            "{:?} is blocked across iterations of the loop at {:?}, which is re-entered from {:?}",
+            carried_place, self.loop_head, back_edge_source
+        ))
+    }
+}
+
+// `ConflictAcrossBackEdge` identifies places which a loop abstraction edge at a
+// loop head keeps blocked across iterations, and mutably borrows each of them
+// right before every back edge to that loop head
+#[derive(Default)]
+pub struct ConflictAcrossBackEdge {
+    // The loop heads of the current body that no mutants were generated for yet,
+    // with the sources of their back edges
+    loop_heads: BodyCache<HashMap<BasicBlock, Vec<BasicBlock>>>,
+}
+
+impl Mutation for ConflictAcrossBackEdge {
+    fn make_stream<'a, 'mir: 'a, 'tcx: 'mir>(
+        &self,
+        ctx: CompilerCtxt<'a, 'tcx>,
+        body: &'a Body<'tcx>,
+        curr: PcgLocation<'tcx>,
+        _next: PcgLocation<'tcx>,
+        phases: PhasePair,
+    ) -> MutantStream<'a, 'mir, 'tcx> {
+        let loop_head = curr.location.block;
+
+        // Only generate mutants once per loop, from the first site at the start of its
+        // head. A head without statements is visited at its terminator.
+        let back_edge_sources = if curr.location.statement_index == 0 {
+            self.loop_heads
+                .with_mut(body, || loop_heads(body), |loop_heads| loop_heads.remove(&loop_head))
+                .unwrap_or_default()
+        } else {
+            vec![]
+        };
+
+        let mut carried = vec![];
+        if !back_edge_sources.is_empty() {
+            let borrows_graph = curr.states[phases.curr]
+                .borrow_pcg()
+                .graph();
+            let blocked_by_loop: HashSet<Place<'tcx>> = borrows_graph
+                .edges()
+                .filter(|edge_ref| {
+                    matches!(
+                        edge_ref.kind(),
+                        BorrowPcgEdgeKind::Abstraction(AbstractionType::Loop(_))
+                    )
+                })
+                .flat_map(|edge_ref| edge_ref.blocked_nodes(ctx).collect::<Vec<_>>())
+                .flat_map(|node| node.try_to_local_node(ctx))
+                .flat_map(local_node_to_current_place)
+                .filter(|place| has_named_local(*place, body))
+                .collect();
+            for place in blocked_by_loop.into_iter() {
+                for back_edge_source in back_edge_sources.iter() {
+                    carried.push((place, *back_edge_source));
+                }
+            }
+        }
+
+        MutantStream::new(Box::new(Iter {
+            carried,
+            ctx,
+            body,
+            loop_head,
+        }))
+    }
+    fn name(&self) -> String {
+        "conflict-across-back-edge".into()
+    }
+}
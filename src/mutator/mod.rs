@@ -1,13 +1,36 @@
+pub mod assign_to_borrowed;
+pub mod closure_capture;
+pub mod double_mut_borrow;
+pub mod drop_while_borrowed;
+pub mod escaping_reference;
 pub mod expiry_order;
+pub mod loop_back_edge;
 pub mod move_from_borrowed;
+pub mod move_out_of_reference;
 pub mod mutably_lend_shared;
 pub mod mutator_impl;
+pub mod partial_move;
+pub mod positive_control;
+pub mod write_through_shared;
+pub mod write_to_relabelled;
 pub mod write_to_shared;
+pub mod read_from_mutably_lent;
 pub mod read_from_write;
+pub mod reborrow_through_deref;
+pub mod region_projection;
+pub mod remote_place;
+pub mod sibling_borrow;
+pub mod statement_reorder;
+pub mod storage_dead_while_borrowed;
+pub mod two_phase_borrow;
+pub mod use_after_move;
+pub mod validate;
 pub(crate) mod utils;
 
 pub use self::mutator_impl::Mutant;
+pub use self::mutator_impl::MutantBuilder;
 pub use self::mutator_impl::MutantLocation;
 pub use self::mutator_impl::MutantRange;
 pub use self::mutator_impl::Mutator;
 pub use self::mutator_impl::Mutation;
+pub use self::mutator_impl::PhasePair;
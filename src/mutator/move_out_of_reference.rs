@@ -0,0 +1,195 @@
+use super::utils::bogus_source_info;
+use super::utils::has_named_local;
+use super::utils::is_behind_ref;
+use super::utils::is_copy;
+use super::utils::places_in_pcg;
+
+use std::collections::HashSet;
+
+use super::mutator_impl::Mutant;
+use super::mutator_impl::MutantBuilder;
+use super::mutator_impl::MutantIterator;
+use super::mutator_impl::MutantStream;
+use super::mutator_impl::Mutation;
+use super::mutator_impl::PhasePair;
+
+use crate::rustc_interface::middle::mir::Body;
+use crate::rustc_interface::middle::mir::Const;
+use crate::rustc_interface::middle::mir::ConstOperand;
+use crate::rustc_interface::middle::mir::Operand;
+use crate::rustc_interface::middle::mir::Place as MirPlace;
+use crate::rustc_interface::middle::mir::PlaceRef;
+use crate::rustc_interface::middle::mir::ProjectionElem;
+use crate::rustc_interface::middle::mir::Rvalue;
+use crate::rustc_interface::middle::mir::StatementKind;
+use crate::rustc_interface::middle::ty::TyKind;
+
+use pcg::free_pcs::PcgLocation;
+use pcg::utils::CompilerCtxt;
+
+// Returns the MIR places of every place in the PCG with a named local,
+// where references are replaced by the places they point to
+fn candidate_places<'tcx>(
+    ctx: CompilerCtxt<'_, 'tcx>,
+    body: &Body<'tcx>,
+    curr: &PcgLocation<'tcx>,
+    next: &PcgLocation<'tcx>,
+    phases: PhasePair,
+) -> HashSet<MirPlace<'tcx>> {
+    let tcx = ctx.tcx();
+    places_in_pcg(ctx, curr, next, phases)
+        .into_iter()
+        .filter(|place| has_named_local(*place, body))
+        .map(|place| PlaceRef::from(*place).to_place(tcx))
+        .map(|place| {
+            if place.ty(&body.local_decls, tcx).ty.is_ref() {
+                tcx.mk_place_deref(place)
+            } else {
+                place
+            }
+        })
+        .collect()
+}
+
+enum MoveOut<'tcx> {
+    // Move out of a place behind a reference
+    Deref(MirPlace<'tcx>),
+    // Move an element out of an array or slice place
+    Index(MirPlace<'tcx>),
+}
+
+struct Iter<'a, 'tcx: 'a> {
+    moves: Vec<MoveOut<'tcx>>,
+    ctx: CompilerCtxt<'a, 'tcx>,
+    body: &'a Body<'tcx>,
+    curr: PcgLocation<'tcx>,
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> MutantIterator<'a, 'mir, 'tcx> for Iter<'a, 'tcx> {
+    fn next(&mut self) -> Option<Mutant<'tcx>> {
+        while !self.moves.is_empty() {
+            if let Some(mutant) = self.maybe_next() {
+                return Some(mutant);
+            }
+        }
+        return None;
+    }
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> Iter<'a, 'tcx> {
+    fn maybe_next(&mut self) -> Option<Mutant<'tcx>> {
+        let tcx = self.ctx.tcx();
+        let move_out = self.moves.pop()?;
+        let mut builder = MutantBuilder::new(tcx, self.body);
+
+        // Compute the place to move out of between `curr` and `next`
+        let (location, moved_place) = match move_out {
+            MoveOut::Deref(place) => (self.curr.location, place),
+            MoveOut::Index(place) => {
+                let index_local = builder.fresh_local(tcx.types.usize);
+                let zero = Operand::Constant(Box::new(ConstOperand {
+                    span: bogus_source_info(builder.body()).span,
+                    user_ty: None,
+                    const_: Const::from_usize(tcx, 0),
+                }));
+                let index_location = builder.insert_after(
+                    self.curr.location,
+                    StatementKind::Assign(Box::new((
+                        MirPlace::from(index_local),
+                        Rvalue::Use(zero),
+                    ))),
+                )?;
+                let indexed_place =
+                    tcx.mk_place_elem(place, ProjectionElem::Index(index_local));
+                (index_location, indexed_place)
+            }
+        };
+
+        let moved_place_ty = moved_place.ty(&builder.body().local_decls, tcx).ty;
+        let fresh_local = builder.fresh_local(moved_place_ty);
+
+        // Move `moved_place` into a `fresh_local`
+        builder.insert_after(
+            location,
+            StatementKind::Assign(Box::new((
+                MirPlace::from(fresh_local),
+                Rvalue::Use(Operand::Move(moved_place)),
+            ))),
+        )?;
+
+        builder.build(format!("{:?} is behind a reference or an index", moved_place))
+    }
+}
+
+// `MoveOutOfDeref` creates mutants which move a non-`Copy` value out of a place
+// behind a reference
+pub struct MoveOutOfDeref;
+
+impl Mutation for MoveOutOfDeref {
+    fn make_stream<'a, 'mir: 'a, 'tcx: 'mir>(
+        &self,
+        ctx: CompilerCtxt<'a, 'tcx>,
+        body: &'a Body<'tcx>,
+        curr: PcgLocation<'tcx>,
+        next: PcgLocation<'tcx>,
+        phases: PhasePair,
+    ) -> MutantStream<'a, 'mir, 'tcx> {
+        let tcx = ctx.tcx();
+        let moves = candidate_places(ctx, body, &curr, &next, phases)
+            .into_iter()
+            .filter(|place| is_behind_ref(tcx, body, *place))
+            .filter(|place| {
+                let ty = place.ty(&body.local_decls, tcx).ty;
+                !is_copy(tcx, body, ty) && ty.is_sized(tcx, body.typing_env(tcx))
+            })
+            .map(MoveOut::Deref)
+            .collect();
+
+        MutantStream::new(Box::new(Iter {
+            moves,
+            ctx,
+            body,
+            curr,
+        }))
+    }
+    fn name(&self) -> String {
+        "move-out-of-deref".into()
+    }
+}
+
+// `MoveOutOfIndex` creates mutants which move a non-`Copy` element out of an
+// array or slice
+pub struct MoveOutOfIndex;
+
+impl Mutation for MoveOutOfIndex {
+    fn make_stream<'a, 'mir: 'a, 'tcx: 'mir>(
+        &self,
+        ctx: CompilerCtxt<'a, 'tcx>,
+        body: &'a Body<'tcx>,
+        curr: PcgLocation<'tcx>,
+        next: PcgLocation<'tcx>,
+        phases: PhasePair,
+    ) -> MutantStream<'a, 'mir, 'tcx> {
+        let tcx = ctx.tcx();
+        let moves = candidate_places(ctx, body, &curr, &next, phases)
+            .into_iter()
+            .filter(|place| match place.ty(&body.local_decls, tcx).ty.kind() {
+                TyKind::Array(elem_ty, _) | TyKind::Slice(elem_ty) => {
+                    !is_copy(tcx, body, *elem_ty)
+                }
+                _ => false,
+            })
+            .map(MoveOut::Index)
+            .collect();
+
+        MutantStream::new(Box::new(Iter {
+            moves,
+            ctx,
+            body,
+            curr,
+        }))
+    }
+    fn name(&self) -> String {
+        "move-out-of-index".into()
+    }
+}
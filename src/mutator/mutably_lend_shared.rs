@@ -1,30 +1,22 @@
 use super::utils::borrowed_places;
-use super::utils::fresh_local;
 use super::utils::has_named_local;
 use super::utils::is_shared;
 
 use std::collections::HashSet;
 
 use super::mutator_impl::Mutant;
+use super::mutator_impl::MutantBuilder;
 use super::mutator_impl::MutantIterator;
-use super::mutator_impl::MutantLocation;
-use super::mutator_impl::MutantRange;
 use super::mutator_impl::MutantStream;
 use super::mutator_impl::Mutation;
+use super::mutator_impl::PhasePair;
 
 use crate::rustc_interface::middle::mir::Body;
 use crate::rustc_interface::middle::mir::BorrowKind;
 use crate::rustc_interface::middle::mir::MutBorrowKind;
-use crate::rustc_interface::middle::mir::Place as MirPlace;
 use crate::rustc_interface::middle::mir::PlaceRef;
-use crate::rustc_interface::middle::mir::Rvalue;
-use crate::rustc_interface::middle::mir::Statement;
-use crate::rustc_interface::middle::mir::StatementKind;
-use crate::rustc_interface::middle::ty::Region;
-use crate::rustc_interface::middle::ty::Ty;
 
 use pcg::free_pcs::PcgLocation;
-use pcg::pcg::EvalStmtPhase;
 use pcg::utils::CompilerCtxt;
 use pcg::utils::Place;
 
@@ -32,7 +24,7 @@ use pcg::utils::Place;
 pub struct MutablyLendShared;
 
 struct Iter<'a, 'tcx: 'a> {
-    immutably_lent: Vec<(Place<'tcx>, Region<'tcx>)>,
+    immutably_lent: Vec<Place<'tcx>>,
     ctx: CompilerCtxt<'a, 'tcx>,
     body: &'a Body<'tcx>,
     curr: PcgLocation<'tcx>,
@@ -51,53 +43,19 @@ impl<'a, 'mir: 'a, 'tcx: 'mir> MutantIterator<'a, 'mir, 'tcx> for Iter<'a, 'tcx>
 
 impl<'a, 'mir: 'a, 'tcx: 'mir> Iter<'a, 'tcx> {
     fn maybe_next(&mut self) -> Option<Mutant<'tcx>> {
-        let (place, region) = self.immutably_lent.pop()?;
+        let place = self.immutably_lent.pop()?;
         let lent_place = PlaceRef::from(place).to_place(self.ctx.tcx());
-        let mut mutant_body = self.body.clone();
-
-        let borrow_ty = Ty::new_mut_ref(
-            self.ctx.tcx(),
-            region,
-            lent_place.ty(&self.body.local_decls, self.ctx.tcx()).ty,
-        );
-
-        let fresh_local = fresh_local(&mut mutant_body, borrow_ty);
-
-        let statement_index = self.curr.location.statement_index;
-        let bb_index = self.curr.location.block;
-        let bb = mutant_body.basic_blocks_mut().get_mut(bb_index)?;
-        let statement_source_info = bb.statements.get(statement_index)?.source_info;
+        let mut builder = MutantBuilder::new(self.ctx.tcx(), self.body);
 
         let default_mut_borrow = BorrowKind::Mut {
             kind: MutBorrowKind::Default,
         };
 
-        // Statement which mutably borrows `lent_place` into a `fresh_local`
-        let new_borrow = Statement {
-            source_info: statement_source_info,
-            kind: StatementKind::Assign(Box::new((
-                MirPlace::from(fresh_local),
-                Rvalue::Ref(region, default_mut_borrow, lent_place),
-            ))),
-        };
-        let info = format!("{:?} was shared, so inserted {:?}", lent_place, &new_borrow);
-
-        // Insert `new_borrow` between `curr` and `next`
-        bb.statements.insert(statement_index + 1, new_borrow);
-
-        let borrow_loc = MutantLocation {
-            basic_block: self.curr.location.block.index(),
-            statement_index: statement_index + 1,
-        };
+        // Mutably borrow `lent_place` into a fresh local between `curr` and `next`
+        let (_, new_borrow) = builder.fresh_ref_local(lent_place, default_mut_borrow);
+        builder.insert_after(self.curr.location, new_borrow)?;
 
-        Some(Mutant {
-            body: mutant_body,
-            range: MutantRange {
-                start: borrow_loc.clone(),
-                end: borrow_loc,
-            },
-            info,
-        })
+        builder.build(format!("{:?} was shared", lent_place))
     }
 }
 
@@ -108,14 +66,15 @@ impl Mutation for MutablyLendShared {
         body: &'a Body<'tcx>,
         curr: PcgLocation<'tcx>,
         next: PcgLocation<'tcx>,
+        phases: PhasePair,
     ) -> MutantStream<'a, 'mir, 'tcx> {
         let immutably_lent_in_curr = {
-            let borrows_graph = curr.states[EvalStmtPhase::PostMain].borrow_pcg().graph();
+            let borrows_graph = curr.states[phases.curr].borrow_pcg().graph();
             borrowed_places(borrows_graph, is_shared)
         };
 
         let immutably_lent_in_next = {
-            let borrows_graph = next.states[EvalStmtPhase::PostOperands]
+            let borrows_graph = next.states[phases.next]
                 .borrow_pcg()
                 .graph();
             borrowed_places(borrows_graph, is_shared)
@@ -126,6 +85,7 @@ impl Mutation for MutablyLendShared {
         let immutably_lent = immutably_lent_in_curr
             .filter(|(place, _)| immutably_lent_in_next.contains(place))
             .filter(|(place, _)| has_named_local(*place, body))
+            .map(|(place, _)| place)
             .collect();
 
         MutantStream::new(Box::new(Iter {
@@ -1,9 +1,37 @@
 use serde::Serialize;
 
+use crate::ExpectedOutcome;
+
+use super::utils::bogus_source_info;
+use super::utils::BodyCache;
+use super::utils::fresh_basic_block;
+use super::utils::fresh_local;
+use super::utils::is_copy;
+use super::utils::split_with_imaginary_branch;
+
 use crate::rustc_interface::middle::mir::BasicBlock;
 use crate::rustc_interface::middle::mir::Body;
+use crate::rustc_interface::middle::mir::BorrowKind;
+use crate::rustc_interface::middle::mir::Local;
+use crate::rustc_interface::middle::mir::Location;
+use crate::rustc_interface::middle::mir::Operand;
+use crate::rustc_interface::middle::mir::Place as MirPlace;
+use crate::rustc_interface::middle::mir::Rvalue;
+use crate::rustc_interface::middle::mir::Statement;
+use crate::rustc_interface::middle::mir::StatementKind;
+use crate::rustc_interface::middle::mir::Terminator;
+use crate::rustc_interface::middle::mir::TerminatorKind;
+use crate::rustc_interface::middle::ty::fold::fold_regions;
+use crate::rustc_interface::middle::ty::Region;
+use crate::rustc_interface::middle::ty::RegionKind;
+use crate::rustc_interface::middle::ty::RegionVid;
+use crate::rustc_interface::middle::ty::Ty;
+use crate::rustc_interface::middle::ty::TyCtxt;
+use crate::rustc_interface::middle::ty::TypeFoldable;
 
 use pcg::free_pcs::PcgLocation;
+use pcg::pcg::BodyWithBorrowckFacts;
+use pcg::pcg::EvalStmtPhase;
 use pcg::utils::CompilerCtxt;
 use pcg::PcgOutput;
 
@@ -30,6 +58,262 @@ pub struct Mutant<'tcx> {
     pub info: String,
 }
 
+impl From<Location> for MutantLocation {
+    fn from(location: Location) -> Self {
+        Self {
+            basic_block: location.block.index(),
+            statement_index: location.statement_index,
+        }
+    }
+}
+
+// A `MutantBuilder` edits a copy of a MIR `Body` to create a `Mutant`. It records
+// the location of every statement and terminator it inserts or moves, in order,
+// so that the `MutantRange` spans from the first to the last of them and the
+// `info` describes each edit.
+pub struct MutantBuilder<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    body: Body<'tcx>,
+    // The smallest region variable which does not occur in `body`
+    next_region_vid: RegionVid,
+    locations: Vec<Location>,
+    edits: Vec<String>,
+}
+
+thread_local! {
+    // The smallest region variable which does not occur in the body that mutants
+    // are currently built from. Scanning the body for it is as expensive as
+    // cloning it, so it is only done once per body.
+    static NEXT_REGION_VID: BodyCache<RegionVid> = BodyCache::default();
+}
+
+impl<'tcx> MutantBuilder<'tcx> {
+    pub fn new(tcx: TyCtxt<'tcx>, body: &Body<'tcx>) -> Self {
+        let next_region_vid = NEXT_REGION_VID.with(|next_region_vid| {
+            next_region_vid.get_or_compute(body, || {
+                let mut next_region_vid = RegionVid::ZERO;
+                tcx.for_each_free_region(body, |region| {
+                    if let RegionKind::ReVar(vid) = region.kind()
+                        && vid >= next_region_vid
+                    {
+                        next_region_vid = vid + 1;
+                    }
+                });
+                next_region_vid
+            })
+        });
+        Self {
+            tcx,
+            body: body.clone(),
+            next_region_vid,
+            locations: vec![],
+            edits: vec![],
+        }
+    }
+
+    pub fn body(&self) -> &Body<'tcx> {
+        &self.body
+    }
+
+    // Allocate a region variable which does not occur anywhere else in the body, in
+    // the same way that `renumber.rs` in the borrow checker does
+    pub fn fresh_region(&mut self) -> Region<'tcx> {
+        let vid = self.next_region_vid;
+        self.next_region_vid = vid + 1;
+        Region::new_var(self.tcx, vid)
+    }
+
+    // Replace every free region in `value` with a fresh region variable
+    fn renumber_regions<T: TypeFoldable<TyCtxt<'tcx>>>(&mut self, value: T) -> T {
+        let tcx = self.tcx;
+        fold_regions(tcx, value, |_, _| self.fresh_region())
+    }
+
+    // Create a fresh local of type `ty`. The regions in `ty` are replaced with fresh
+    // region variables, so they may be erased.
+    pub fn fresh_local(&mut self, ty: Ty<'tcx>) -> Local {
+        let ty = self.renumber_regions(ty);
+        fresh_local(&mut self.body, ty)
+    }
+
+    // Create a fresh local which can hold a borrow of `place` of kind `kind`, along
+    // with the statement which performs that borrow
+    pub fn fresh_ref_local(
+        &mut self,
+        place: MirPlace<'tcx>,
+        kind: BorrowKind,
+    ) -> (Local, StatementKind<'tcx>) {
+        let erased_region = Region::new_from_kind(self.tcx, RegionKind::ReErased);
+        let place_ty = place.ty(&self.body.local_decls, self.tcx).ty;
+        let ref_ty = Ty::new_ref(self.tcx, erased_region, place_ty, kind.to_mutbl_lossy());
+        let ref_local = self.fresh_local(ref_ty);
+        let borrow = StatementKind::Assign(Box::new((
+            MirPlace::from(ref_local),
+            Rvalue::Ref(self.fresh_region(), kind, place),
+        )));
+        (ref_local, borrow)
+    }
+
+    // Create a fresh local which can hold a read of `place`, along with the statement
+    // which performs that read. `Copy` places are copied and anything else is read
+    // through a shared borrow.
+    pub fn fresh_read_local(&mut self, place: MirPlace<'tcx>) -> (Local, StatementKind<'tcx>) {
+        let place_ty = place.ty(&self.body.local_decls, self.tcx).ty;
+        if !is_copy(self.tcx, &self.body, place_ty) {
+            return self.fresh_ref_local(place, BorrowKind::Shared);
+        }
+        let read_local = self.fresh_local(place_ty);
+        let read = StatementKind::Assign(Box::new((
+            MirPlace::from(read_local),
+            Rvalue::Use(Operand::Copy(place)),
+        )));
+        (read_local, read)
+    }
+
+    // Insert a statement of kind `kind` right after `location`, with the source
+    // info of the statement at `location`. If `location` is a terminator, the
+    // statement is inserted right before it, at the end of the block; at a CFG edge
+    // site the `Mutator` then moves it onto the edge. Returns the location of the
+    // new statement, or `None` if there is no such location.
+    pub fn insert_after(
+        &mut self,
+        location: Location,
+        kind: StatementKind<'tcx>,
+    ) -> Option<Location> {
+        let bb = self.body.basic_blocks_mut().get_mut(location.block)?;
+        if location.statement_index == bb.statements.len() {
+            return self.insert_before(location, kind);
+        }
+        let source_info = bb.statements.get(location.statement_index)?.source_info;
+        let statement = Statement { source_info, kind };
+        let inserted_location = location.successor_within_block();
+        self.locations.push(inserted_location);
+        self.edits.push(format!("inserted {:?}", &statement));
+        bb.statements.insert(inserted_location.statement_index, statement);
+        Some(inserted_location)
+    }
+
+    // Insert a statement of kind `kind` right before the statement or terminator at
+    // `location`, with its source info. Returns the location of the new statement.
+    pub fn insert_before(
+        &mut self,
+        location: Location,
+        kind: StatementKind<'tcx>,
+    ) -> Option<Location> {
+        let bb = self.body.basic_blocks_mut().get_mut(location.block)?;
+        let source_info = match bb.statements.get(location.statement_index) {
+            Some(statement) => statement.source_info,
+            None if location.statement_index == bb.statements.len() => {
+                bb.terminator.as_ref()?.source_info
+            }
+            None => return None,
+        };
+        let statement = Statement { source_info, kind };
+        self.locations.push(location);
+        self.edits.push(format!("inserted {:?}", &statement));
+        bb.statements.insert(location.statement_index, statement);
+        Some(location)
+    }
+
+    // Append a statement of kind `kind` to the end of `block`, with the source info
+    // of its terminator
+    pub fn push_statement(&mut self, block: BasicBlock, kind: StatementKind<'tcx>) -> Location {
+        let bogus_source_info = bogus_source_info(&self.body);
+        let bb = &mut self.body.basic_blocks_mut()[block];
+        let statement = Statement {
+            source_info: bb
+                .terminator
+                .as_ref()
+                .map_or(bogus_source_info, |terminator| terminator.source_info),
+            kind,
+        };
+        let location = Location {
+            block,
+            statement_index: bb.statements.len(),
+        };
+        self.locations.push(location);
+        self.edits.push(format!("inserted {:?}", &statement));
+        bb.statements.push(statement);
+        location
+    }
+
+    // Create an empty block that ends in `terminator`. Statements can be added to
+    // it with `push_statement`.
+    pub fn fresh_block(&mut self, terminator: TerminatorKind<'tcx>) -> BasicBlock {
+        let source_info = bogus_source_info(&self.body);
+        let block = fresh_basic_block(&mut self.body);
+        self.body.basic_blocks_mut()[block].terminator = Some(Terminator {
+            source_info,
+            kind: terminator,
+        });
+        block
+    }
+
+    // See `utils::split_with_imaginary_branch`. Returns the fresh block containing
+    // `statements` and `terminator`, or `None` if `location` is not a statement.
+    pub fn split_with_imaginary_branch(
+        &mut self,
+        location: Location,
+        statements: Vec<StatementKind<'tcx>>,
+        terminator: TerminatorKind<'tcx>,
+    ) -> Option<BasicBlock> {
+        let source_info = bogus_source_info(&self.body);
+        let statements: Vec<_> = statements
+            .into_iter()
+            .map(|kind| Statement { source_info, kind })
+            .collect();
+        let num_statements = statements.len();
+        let edits: Vec<String> = statements
+            .iter()
+            .map(|statement| format!("{:?}", statement))
+            .chain(std::iter::once(format!("{:?}", &terminator)))
+            .map(|inserted| format!("inserted {} on an imaginary branch", inserted))
+            .collect();
+
+        let block = split_with_imaginary_branch(&mut self.body, location, statements, terminator)?;
+        self.edits.extend(edits);
+        self.locations.extend((0..=num_statements).map(|statement_index| Location {
+            block,
+            statement_index,
+        }));
+        Some(block)
+    }
+
+    // Move the statement at `location` right after the statement at `target`, which
+    // comes later in the same block
+    pub fn move_after(&mut self, location: Location, target: Location) -> Option<()> {
+        if location.block != target.block || target.statement_index <= location.statement_index {
+            return None;
+        }
+        let bb = self.body.basic_blocks_mut().get_mut(location.block)?;
+        bb.statements.get(target.statement_index)?;
+        self.edits.push(format!(
+            "moved {:?} after {:?}",
+            &bb.statements[location.statement_index],
+            &bb.statements[target.statement_index]
+        ));
+        let statement = bb.statements.remove(location.statement_index);
+        bb.statements.insert(target.statement_index, statement);
+        self.locations.push(location);
+        self.locations.push(target);
+        Some(())
+    }
+
+    // Finish the mutant. Its `info` explains that `reason` led to the edits.
+    // Returns `None` if nothing was edited.
+    pub fn build(self, reason: impl std::fmt::Display) -> Option<Mutant<'tcx>> {
+        let range = MutantRange {
+            start: (*self.locations.first()?).into(),
+            end: (*self.locations.last()?).into(),
+        };
+        Some(Mutant {
+            body: self.body,
+            range,
+            info: format!("{}, so {}", reason, self.edits.join(" and ")),
+        })
+    }
+}
+
 pub struct MutantStream<'a, 'mir: 'a, 'tcx: 'mir> {
     // ctx: CompilerCtxt<'tcx, 'tcx>,
     // body: &'tcx Body<'tcx>,
@@ -51,6 +335,44 @@ pub trait MutantIterator<'a, 'mir, 'tcx> {
     fn next(&mut self) -> Option<Mutant<'tcx>>;
 }
 
+// The PCG evaluation phases a `Mutation` inspects: `curr.states[curr]` is the state
+// before the mutant and `next.states[next]` the state after it
+#[derive(Clone, Copy, Debug)]
+pub struct PhasePair {
+    pub curr: EvalStmtPhase,
+    pub next: EvalStmtPhase,
+    // If set, `curr` and `next` are the same assignment and mutants are inserted
+    // after its operands are evaluated and before its destination is written. This
+    // compares the PCG states between phases of a single statement.
+    pub within_statement: bool,
+}
+
+impl PhasePair {
+    // Compare the state after the main effect of one statement with the state
+    // after the operands of the next statement are evaluated
+    pub fn between_statements() -> Self {
+        Self {
+            curr: EvalStmtPhase::PostMain,
+            next: EvalStmtPhase::PostOperands,
+            within_statement: false,
+        }
+    }
+
+    pub fn within_statement(curr: EvalStmtPhase, next: EvalStmtPhase) -> Self {
+        Self {
+            curr,
+            next,
+            within_statement: true,
+        }
+    }
+}
+
+impl Default for PhasePair {
+    fn default() -> Self {
+        Self::between_statements()
+    }
+}
+
 // A `Mutation` uses a MIR `Body` and the analyses for two consecutive
 // statements to produce a set of mutant MIR `Body`s.
 pub trait Mutation {
@@ -60,8 +382,176 @@ pub trait Mutation {
         body: &'a Body<'tcx>,
         curr: PcgLocation<'tcx>,
         next: PcgLocation<'tcx>,
+        phases: PhasePair,
     ) -> MutantStream<'a, 'mir, 'tcx>;
     fn name(&self) -> String;
+    // Called before the `Mutation` is run on `body`, so that it can compute what it
+    // needs from the borrow checker's results for `body`
+    fn prepare(&self, _body: &BodyWithBorrowckFacts<'_>) {}
+    // Whether the borrow checker should accept the mutants this `Mutation` creates.
+    // Most mutations introduce borrow errors, so by default it should reject them.
+    fn expected_outcome(&self) -> ExpectedOutcome {
+        ExpectedOutcome::Fail
+    }
+}
+
+// The CFG edge from `source` to `target`. Mutants generated at the end of `source`
+// are moved into a fresh block on this edge.
+#[derive(Clone, Copy)]
+struct CfgEdge {
+    source: BasicBlock,
+    target: BasicBlock,
+    // The number of statements in `source` before mutation
+    num_source_statements: usize,
+    // The number of blocks in the body before mutation
+    num_blocks: usize,
+}
+
+impl CfgEdge {
+    // Move the statements `mutant` inserted at the end of `source` into a fresh
+    // block which jumps to `target`, and redirect the edge through that block.
+    // Returns `None` if the mutant added blocks, e.g. because it split `source`, as
+    // its edits cannot be moved onto the edge.
+    fn move_to_edge_block<'tcx>(&self, mut mutant: Mutant<'tcx>) -> Option<Mutant<'tcx>> {
+        if mutant.body.basic_blocks.len() != self.num_blocks {
+            return None;
+        }
+        let inserted: Vec<_> = mutant.body.basic_blocks_mut()[self.source]
+            .statements
+            .drain(self.num_source_statements..)
+            .collect();
+        if inserted.is_empty() {
+            return Some(mutant);
+        }
+
+        let source_info = mutant.body.basic_blocks[self.source].terminator().source_info;
+        let edge_bb_index = fresh_basic_block(&mut mutant.body);
+        let edge_bb = mutant.body.basic_blocks_mut().get_mut(edge_bb_index).unwrap();
+        edge_bb.statements = inserted;
+        edge_bb.terminator = Some(Terminator {
+            source_info,
+            kind: TerminatorKind::Goto {
+                target: self.target,
+            },
+        });
+
+        for successor in mutant.body.basic_blocks_mut()[self.source]
+            .terminator_mut()
+            .kind
+            .successors_mut()
+        {
+            if *successor == self.target {
+                *successor = edge_bb_index;
+            }
+        }
+
+        for mutant_location in [&mut mutant.range.start, &mut mutant.range.end] {
+            if mutant_location.basic_block == self.source.index()
+                && mutant_location.statement_index >= self.num_source_statements
+            {
+                mutant_location.basic_block = edge_bb_index.index();
+                mutant_location.statement_index -= self.num_source_statements;
+            }
+        }
+
+        Some(mutant)
+    }
+}
+
+// An assignment which is split in two for mutants generated within it: its rvalue
+// is first assigned to a fresh temporary, and the temporary is then moved into the
+// original destination. Mutants inserted right before the assignment are moved in
+// between, after its operands are evaluated and before its destination is written.
+#[derive(Clone, Copy)]
+struct StatementSplit {
+    location: Location,
+    // The number of statements in the block of `location` before mutation
+    num_statements: usize,
+    // The number of blocks in the body before mutation
+    num_blocks: usize,
+}
+
+impl StatementSplit {
+    // Returns `None` unless `mutant` only inserted statements right before the
+    // assignment, e.g. because it moved statements or split a block, as there is
+    // no point between the phases of the assignment to move such edits to.
+    fn move_between_phases<'tcx>(
+        &self,
+        tcx: TyCtxt<'tcx>,
+        body: &Body<'tcx>,
+        mut mutant: Mutant<'tcx>,
+    ) -> Option<Mutant<'tcx>> {
+        if mutant.body.basic_blocks.len() != self.num_blocks {
+            return None;
+        }
+        let block = self.location.block;
+        let index = self.location.statement_index;
+        let original = &body.basic_blocks[block].statements;
+        let statements = &mutant.body.basic_blocks[block].statements;
+        let num_inserted = statements.len().checked_sub(self.num_statements)?;
+        if num_inserted == 0 {
+            return None;
+        }
+        let unchanged = |statements: &[Statement<'tcx>], original: &[Statement<'tcx>]| {
+            statements
+                .iter()
+                .zip(original)
+                .all(|(statement, original)| statement.kind == original.kind)
+        };
+        if !unchanged(&statements[..index], &original[..index])
+            || !unchanged(&statements[index + num_inserted..], &original[index..])
+        {
+            return None;
+        }
+
+        let assign = mutant.body.basic_blocks_mut()[block]
+            .statements
+            .remove(index + num_inserted);
+        let source_info = assign.source_info;
+        let StatementKind::Assign(assign) = assign.kind else {
+            return None;
+        };
+        let (place, rvalue) = *assign;
+        // The temporary has the type of the destination, including its regions, so
+        // the split adds no constraints between regions
+        let temp = fresh_local(&mut mutant.body, place.ty(&mutant.body.local_decls, tcx).ty);
+        let statements = &mut mutant.body.basic_blocks_mut()[block].statements;
+        statements.insert(
+            index,
+            Statement {
+                source_info,
+                kind: StatementKind::Assign(Box::new((MirPlace::from(temp), rvalue))),
+            },
+        );
+        statements.insert(
+            index + num_inserted + 1,
+            Statement {
+                source_info,
+                kind: StatementKind::Assign(Box::new((
+                    place,
+                    Rvalue::Use(Operand::Move(MirPlace::from(temp))),
+                ))),
+            },
+        );
+
+        for mutant_location in [&mut mutant.range.start, &mut mutant.range.end] {
+            if mutant_location.basic_block == block.index()
+                && mutant_location.statement_index >= index
+            {
+                mutant_location.statement_index += 1;
+            }
+        }
+
+        Some(mutant)
+    }
+}
+
+// A pair of PCG locations between which a `Mutation` inserts mutants
+struct MutationSite<'tcx> {
+    curr: PcgLocation<'tcx>,
+    next: PcgLocation<'tcx>,
+    edge: Option<CfgEdge>,
+    split: Option<StatementSplit>,
 }
 
 // A `Mutator` generates mutants for a MIR `Body` using a `Mutation`.
@@ -73,9 +563,13 @@ pub struct Mutator<'a, 'mir: 'a, 'tcx: 'mir> {
     ctx: CompilerCtxt<'a, 'tcx>,
     body: &'a Body<'tcx>,
     mutants: Option<MutantStream<'a, 'mir, 'tcx>>,
+    phases: PhasePair,
     basic_blocks: VecDeque<BasicBlock>,
-    bb_stmts: Option<Vec<PcgLocation<'tcx>>>,
-    stmt_idx: usize,
+    sites: VecDeque<MutationSite<'tcx>>,
+    // The edge of the site that `mutants` was generated for, if any
+    edge: Option<CfgEdge>,
+    // The split assignment of the site that `mutants` was generated for, if any
+    split: Option<StatementSplit>,
     // borrowck: NllBorrowCheckerImpl<'tcx, 'tcx>,
 }
 
@@ -85,6 +579,7 @@ impl<'a, 'mir: 'a, 'tcx: 'mir> Mutator<'a, 'mir, 'tcx> {
         ctx: CompilerCtxt<'a, 'tcx>,
         analysis: &'a mut PcgOutput<'mir, 'tcx, System>,
         body: &'a Body<'tcx>,
+        phases: PhasePair,
     ) -> Self {
         Self {
             mutation,
@@ -92,9 +587,11 @@ impl<'a, 'mir: 'a, 'tcx: 'mir> Mutator<'a, 'mir, 'tcx> {
             ctx,
             body,
             mutants: None,
+            phases,
             basic_blocks: body.basic_blocks.indices().collect(),
-            bb_stmts: None,
-            stmt_idx: 0,
+            sites: VecDeque::new(),
+            edge: None,
+            split: None,
         }
     }
 
@@ -102,8 +599,109 @@ impl<'a, 'mir: 'a, 'tcx: 'mir> Mutator<'a, 'mir, 'tcx> {
         self.mutation.name()
     }
 
-    fn curr_bb(&self) -> Option<BasicBlock> {
-        Some(*self.basic_blocks.front()?)
+    // Returns the sites in `bb`: each pair of consecutive statements, including the
+    // last statement and the terminator, and the CFG edge to each successor of `bb`
+    // that has no other predecessor. Edge sites pair the state at the terminator
+    // with the state at the start of the successor, so borrows which expire at
+    // calls and branches can be targeted.
+    fn sites_for_bb(&mut self, bb: BasicBlock) -> VecDeque<MutationSite<'tcx>> {
+        let body = self.body;
+        let Ok(Some(pcg_bb)) = self.analysis.get_all_for_bb(bb) else {
+            return VecDeque::new();
+        };
+
+        if self.phases.within_statement {
+            return self.sites_within_statements(pcg_bb.statements);
+        }
+
+        let mut sites: VecDeque<_> = pcg_bb
+            .statements
+            .windows(2)
+            .map(|locations| MutationSite {
+                curr: locations[0].clone(),
+                next: locations[1].clone(),
+                edge: None,
+                split: None,
+            })
+            .collect();
+
+        // The PCG locations of a block end with its terminator
+        let num_statements = body.basic_blocks[bb].statements.len();
+        let Some(terminator_location) = pcg_bb.statements.get(num_statements) else {
+            return sites;
+        };
+
+        for successor in body.basic_blocks[bb].terminator().successors() {
+            if body.basic_blocks[successor].is_cleanup {
+                continue;
+            }
+            // The entry state of a join block is the join of the states on all of its
+            // incoming edges, not the state on this edge, so only edges into blocks with
+            // a single predecessor are mutated
+            if body.basic_blocks.predecessors()[successor].len() != 1 {
+                continue;
+            }
+            let Ok(Some(successor_pcg_bb)) = self.analysis.get_all_for_bb(successor) else {
+                continue;
+            };
+            let Some(first) = successor_pcg_bb.statements.first() else {
+                continue;
+            };
+            sites.push_back(MutationSite {
+                curr: terminator_location.clone(),
+                next: first.clone(),
+                edge: Some(CfgEdge {
+                    source: bb,
+                    target: successor,
+                    num_source_statements: num_statements,
+                    num_blocks: body.basic_blocks.len(),
+                }),
+                split: None,
+            });
+        }
+
+        sites
+    }
+
+    // Returns a site for each assignment except the first statement in its block.
+    // `curr` and `next` are both that assignment, but `curr` is moved to the
+    // preceding statement so that mutants are inserted right before it. Those
+    // mutants are then moved between the phases of the assignment by splitting it,
+    // see `StatementSplit`. Other statements have no such point, so they get no site.
+    fn sites_within_statements(
+        &self,
+        locations: Vec<PcgLocation<'tcx>>,
+    ) -> VecDeque<MutationSite<'tcx>> {
+        let body = self.body;
+        locations
+            .into_iter()
+            .filter(|location| location.location.statement_index > 0)
+            .filter(|location| {
+                let Location { block, statement_index } = location.location;
+                matches!(
+                    body.basic_blocks[block]
+                        .statements
+                        .get(statement_index)
+                        .map(|statement| &statement.kind),
+                    Some(StatementKind::Assign(_))
+                )
+            })
+            .map(|location| {
+                let mut curr = location.clone();
+                curr.location.statement_index -= 1;
+                let split = StatementSplit {
+                    location: location.location,
+                    num_statements: body.basic_blocks[location.location.block].statements.len(),
+                    num_blocks: body.basic_blocks.len(),
+                };
+                MutationSite {
+                    curr,
+                    next: location,
+                    edge: None,
+                    split: Some(split),
+                }
+            })
+            .collect()
     }
 
     // Return the next `Mutant` that can be generated from this body
@@ -111,41 +709,44 @@ impl<'a, 'mir: 'a, 'tcx: 'mir> Mutator<'a, 'mir, 'tcx> {
         // Seek until we generate some `Mutant`s or finish traversing
         // the body
 
-        while !self.basic_blocks.is_empty() {
+        loop {
             let old_num_bb = self.basic_blocks.len();
-            let old_stmt_idx = self.stmt_idx;
+            let old_num_sites = self.sites.len();
 
             if let Some(mutants) = &mut self.mutants
                 && let Some(mutant) = mutants.next()
             {
-                return Some(mutant);
-            } else if let Some(bb_stmts) = &self.bb_stmts
-                && let Some(curr) = bb_stmts.get(self.stmt_idx)
-                && let Some(next) = bb_stmts.get(self.stmt_idx + 1)
-            {
+                let mutant = match (self.edge, self.split) {
+                    (Some(edge), _) => edge.move_to_edge_block(mutant),
+                    (None, Some(split)) => {
+                        split.move_between_phases(self.ctx.tcx(), self.body, mutant)
+                    }
+                    (None, None) => Some(mutant),
+                };
+                match mutant {
+                    Some(mutant) => return Some(mutant),
+                    // The stream itself makes progress, so skip the sanity check
+                    None => continue,
+                }
+            } else if let Some(site) = self.sites.pop_front() {
                 self.mutants = Some(self.mutation.make_stream(
                     self.ctx,
                     &self.body,
-                    curr.clone(),
-                    next.clone(),
+                    site.curr,
+                    site.next,
+                    self.phases,
                 ));
-                self.stmt_idx += 1;
+                self.edge = site.edge;
+                self.split = site.split;
+            } else if let Some(bb) = self.basic_blocks.pop_front() {
+                self.mutants = None;
+                self.sites = self.sites_for_bb(bb);
             } else {
-                self.stmt_idx = 0;
-                self.bb_stmts = if let Some(curr_bb) = self.curr_bb()
-                    && let Ok(Some(pcg_bb)) = self.analysis.get_all_for_bb(curr_bb)
-                {
-                    Some(pcg_bb.statements)
-                } else {
-                    None
-                };
-                self.basic_blocks.pop_front();
+                return None;
             }
 
             // Sanity check for termination
-            assert!(self.basic_blocks.len() < old_num_bb || self.stmt_idx > old_stmt_idx);
+            assert!(self.basic_blocks.len() < old_num_bb || self.sites.len() < old_num_sites);
         }
-
-        None
     }
 }
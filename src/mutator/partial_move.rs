@@ -0,0 +1,206 @@
+use super::utils::has_named_local;
+use super::utils::is_copy;
+
+use std::collections::HashMap;
+
+use super::mutator_impl::Mutant;
+use super::mutator_impl::MutantBuilder;
+use super::mutator_impl::MutantIterator;
+use super::mutator_impl::MutantStream;
+use super::mutator_impl::Mutation;
+use super::mutator_impl::PhasePair;
+
+use crate::ExpectedOutcome;
+
+use crate::rustc_interface::middle::mir::Body;
+use crate::rustc_interface::middle::mir::Operand;
+use crate::rustc_interface::middle::mir::Place as MirPlace;
+use crate::rustc_interface::middle::mir::PlaceRef;
+use crate::rustc_interface::middle::mir::ProjectionElem;
+use crate::rustc_interface::middle::mir::Rvalue;
+use crate::rustc_interface::middle::mir::StatementKind;
+use crate::rustc_interface::middle::mir::TerminatorKind;
+use crate::rustc_interface::middle::ty::TyCtxt;
+
+use pcg::free_pcs::CapabilityKind;
+use pcg::free_pcs::PcgLocation;
+use pcg::utils::CompilerCtxt;
+
+// Returns the parent of `place` if `place` is a field of an owned struct that
+// fields can be moved out of
+fn struct_parent<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+    place: MirPlace<'tcx>,
+) -> Option<MirPlace<'tcx>> {
+    if place.projection.contains(&ProjectionElem::Deref) {
+        return None;
+    }
+    let (parent, elem) = place.iter_projections().last()?;
+    let adt_def = parent.ty(&body.local_decls, tcx).ty.ty_adt_def()?;
+    match elem {
+        ProjectionElem::Field(..) if adt_def.is_struct() && !adt_def.has_dtor(tcx) => {
+            Some(parent.to_place(tcx))
+        }
+        _ => None,
+    }
+}
+
+// Returns the owned places with a capability at both `curr` and `next`
+fn owned_places<'tcx>(
+    ctx: CompilerCtxt<'_, 'tcx>,
+    body: &Body<'tcx>,
+    curr: &PcgLocation<'tcx>,
+    next: &PcgLocation<'tcx>,
+    phases: PhasePair,
+) -> HashMap<MirPlace<'tcx>, CapabilityKind> {
+    let owned_in_next: HashMap<_, _> = next.states[phases.next]
+        .capabilities()
+        .iter()
+        .collect();
+
+    curr.states[phases.curr]
+        .capabilities()
+        .iter()
+        .filter(|(place, _)| owned_in_next.contains_key(place))
+        .filter(|(place, _)| has_named_local(*place, body))
+        .map(|(place, ck)| (PlaceRef::from(*place).to_place(ctx.tcx()), ck))
+        .collect()
+}
+
+// Returns each field of an expanded struct that can be moved out of, paired with
+// its parent
+fn movable_fields<'tcx>(
+    ctx: CompilerCtxt<'_, 'tcx>,
+    body: &Body<'tcx>,
+    owned: &HashMap<MirPlace<'tcx>, CapabilityKind>,
+) -> Vec<(MirPlace<'tcx>, MirPlace<'tcx>)> {
+    let tcx = ctx.tcx();
+    owned
+        .iter()
+        .filter(|(_, ck)| matches!(ck, CapabilityKind::Exclusive))
+        .filter(|(place, _)| !is_copy(tcx, body, place.ty(&body.local_decls, tcx).ty))
+        .flat_map(|(place, _)| Some((*place, struct_parent(tcx, body, *place)?)))
+        .collect()
+}
+
+struct Iter<'a, 'tcx: 'a> {
+    // Pairs of a field to move out of and a place to read afterwards
+    move_then_read: Vec<(MirPlace<'tcx>, MirPlace<'tcx>)>,
+    ctx: CompilerCtxt<'a, 'tcx>,
+    body: &'a Body<'tcx>,
+    curr: PcgLocation<'tcx>,
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> MutantIterator<'a, 'mir, 'tcx> for Iter<'a, 'tcx> {
+    fn next(&mut self) -> Option<Mutant<'tcx>> {
+        while !self.move_then_read.is_empty() {
+            if let Some(mutant) = self.maybe_next() {
+                return Some(mutant);
+            }
+        }
+        return None;
+    }
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> Iter<'a, 'tcx> {
+    fn maybe_next(&mut self) -> Option<Mutant<'tcx>> {
+        let tcx = self.ctx.tcx();
+        let (moved_place, read_place) = self.move_then_read.pop()?;
+        let mut builder = MutantBuilder::new(tcx, self.body);
+
+        let moved_place_ty = moved_place.ty(&self.body.local_decls, tcx).ty;
+        let moved_local = builder.fresh_local(moved_place_ty);
+
+        let (_, new_read) = builder.fresh_read_local(read_place);
+
+        // Move `moved_place` into a fresh local and then read `read_place` on an
+        // imaginary branch, so that the real path never loses `moved_place`
+        builder.split_with_imaginary_branch(
+            self.curr.location,
+            vec![
+                StatementKind::Assign(Box::new((
+                    MirPlace::from(moved_local),
+                    Rvalue::Use(Operand::Move(moved_place)),
+                ))),
+                new_read,
+            ],
+            TerminatorKind::Unreachable,
+        )?;
+
+        builder.build(format!("{:?} was expanded", read_place))
+    }
+}
+
+// `PartialMoveThenUseParent` creates mutants which move out of a field of an
+// expanded struct and then read the whole struct
+pub struct PartialMoveThenUseParent;
+
+impl Mutation for PartialMoveThenUseParent {
+    fn make_stream<'a, 'mir: 'a, 'tcx: 'mir>(
+        &self,
+        ctx: CompilerCtxt<'a, 'tcx>,
+        body: &'a Body<'tcx>,
+        curr: PcgLocation<'tcx>,
+        next: PcgLocation<'tcx>,
+        phases: PhasePair,
+    ) -> MutantStream<'a, 'mir, 'tcx> {
+        let owned = owned_places(ctx, body, &curr, &next, phases);
+        let move_then_read = movable_fields(ctx, body, &owned);
+
+        MutantStream::new(Box::new(Iter {
+            move_then_read,
+            ctx,
+            body,
+            curr,
+        }))
+    }
+    fn name(&self) -> String {
+        "partial-move-then-use-parent".into()
+    }
+}
+
+// `PartialMoveThenUseSibling` creates mutants which move out of a field of an
+// expanded struct and then read another field of the same struct. The borrow
+// checker should accept all of them.
+pub struct PartialMoveThenUseSibling;
+
+impl Mutation for PartialMoveThenUseSibling {
+    fn make_stream<'a, 'mir: 'a, 'tcx: 'mir>(
+        &self,
+        ctx: CompilerCtxt<'a, 'tcx>,
+        body: &'a Body<'tcx>,
+        curr: PcgLocation<'tcx>,
+        next: PcgLocation<'tcx>,
+        phases: PhasePair,
+    ) -> MutantStream<'a, 'mir, 'tcx> {
+        let tcx = ctx.tcx();
+        let owned = owned_places(ctx, body, &curr, &next, phases);
+        let move_then_read = movable_fields(ctx, body, &owned)
+            .into_iter()
+            .flat_map(|(field, parent)| {
+                owned
+                    .iter()
+                    .filter(move |(sibling, ck)| {
+                        **sibling != field
+                            && struct_parent(tcx, body, **sibling) == Some(parent)
+                            && matches!(ck, CapabilityKind::Read | CapabilityKind::Exclusive)
+                    })
+                    .map(move |(sibling, _)| (field, *sibling))
+            })
+            .collect();
+
+        MutantStream::new(Box::new(Iter {
+            move_then_read,
+            ctx,
+            body,
+            curr,
+        }))
+    }
+    fn name(&self) -> String {
+        "partial-move-then-use-sibling".into()
+    }
+    fn expected_outcome(&self) -> ExpectedOutcome {
+        ExpectedOutcome::Pass
+    }
+}
@@ -0,0 +1,176 @@
+use super::utils::bogus_operand;
+use super::utils::has_named_local;
+use super::utils::is_assignable;
+
+use std::collections::HashMap;
+
+use super::mutator_impl::Mutant;
+use super::mutator_impl::MutantBuilder;
+use super::mutator_impl::MutantIterator;
+use super::mutator_impl::MutantStream;
+use super::mutator_impl::Mutation;
+use super::mutator_impl::PhasePair;
+
+use crate::ExpectedOutcome;
+
+use crate::rustc_interface::middle::mir::Body;
+use crate::rustc_interface::middle::mir::PlaceRef;
+use crate::rustc_interface::middle::mir::Rvalue;
+use crate::rustc_interface::middle::mir::StatementKind;
+
+use pcg::free_pcs::CapabilityKind;
+use pcg::free_pcs::PcgLocation;
+use pcg::utils::CompilerCtxt;
+use pcg::utils::Place;
+
+// Returns the places with a capability satisfying `p` at both `curr` and `next`
+fn places_with_capability<'tcx>(
+    body: &Body<'tcx>,
+    curr: &PcgLocation<'tcx>,
+    next: &PcgLocation<'tcx>,
+    phases: PhasePair,
+    p: fn(CapabilityKind) -> bool,
+) -> Vec<Place<'tcx>> {
+    let capabilities_in_next: HashMap<_, _> = next.states[phases.next]
+        .capabilities()
+        .iter()
+        .collect();
+
+    curr.states[phases.curr]
+        .capabilities()
+        .iter()
+        .filter(|(place, ck)| {
+            p(*ck) && capabilities_in_next.get(place).is_some_and(|next_ck| p(*next_ck))
+        })
+        .filter(|(place, _)| has_named_local(*place, body))
+        .map(|(place, _)| place)
+        .collect()
+}
+
+fn is_readable(ck: CapabilityKind) -> bool {
+    matches!(ck, CapabilityKind::Read | CapabilityKind::Exclusive)
+}
+
+fn is_exclusive(ck: CapabilityKind) -> bool {
+    matches!(ck, CapabilityKind::Exclusive)
+}
+
+struct Iter<'a, 'tcx: 'a> {
+    places: Vec<Place<'tcx>>,
+    // Whether to write to `places` rather than read from them
+    write: bool,
+    ctx: CompilerCtxt<'a, 'tcx>,
+    body: &'a Body<'tcx>,
+    curr: PcgLocation<'tcx>,
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> MutantIterator<'a, 'mir, 'tcx> for Iter<'a, 'tcx> {
+    fn next(&mut self) -> Option<Mutant<'tcx>> {
+        while !self.places.is_empty() {
+            if let Some(mutant) = self.maybe_next() {
+                return Some(mutant);
+            }
+        }
+        return None;
+    }
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> Iter<'a, 'tcx> {
+    fn maybe_next(&mut self) -> Option<Mutant<'tcx>> {
+        let tcx = self.ctx.tcx();
+        let place = self.places.pop()?;
+        let mir_place = PlaceRef::from(*place).to_place(tcx);
+        let mut builder = MutantBuilder::new(tcx, self.body);
+
+        // Statement that writes to `mir_place` or reads it into a fresh local
+        let (new_access, description) = if self.write {
+            let new_value = bogus_operand(tcx, self.body, mir_place)?;
+            (
+                StatementKind::Assign(Box::new((mir_place, Rvalue::Use(new_value)))),
+                "exclusive",
+            )
+        } else {
+            (builder.fresh_read_local(mir_place).1, "readable")
+        };
+
+        // Insert `new_access` between `curr` and `next`
+        builder.insert_after(self.curr.location, new_access)?;
+
+        builder.build(format!("{:?} was {}", mir_place, description))
+    }
+}
+
+// `ReadFromReadable` creates mutants which read from places with R or E capability.
+// The borrow checker should accept all of them.
+pub struct ReadFromReadable;
+
+impl Mutation for ReadFromReadable {
+    fn make_stream<'a, 'mir: 'a, 'tcx: 'mir>(
+        &self,
+        ctx: CompilerCtxt<'a, 'tcx>,
+        body: &'a Body<'tcx>,
+        curr: PcgLocation<'tcx>,
+        next: PcgLocation<'tcx>,
+        phases: PhasePair,
+    ) -> MutantStream<'a, 'mir, 'tcx> {
+        let places = places_with_capability(body, &curr, &next, phases, is_readable);
+
+        MutantStream::new(Box::new(Iter {
+            places,
+            write: false,
+            ctx,
+            body,
+            curr,
+        }))
+    }
+    fn name(&self) -> String {
+        "read-from-readable".into()
+    }
+    fn expected_outcome(&self) -> ExpectedOutcome {
+        ExpectedOutcome::Pass
+    }
+}
+
+// `WriteToExclusive` creates mutants which write to places with E capability.
+// The borrow checker should accept all of them.
+pub struct WriteToExclusive;
+
+impl Mutation for WriteToExclusive {
+    fn make_stream<'a, 'mir: 'a, 'tcx: 'mir>(
+        &self,
+        ctx: CompilerCtxt<'a, 'tcx>,
+        body: &'a Body<'tcx>,
+        curr: PcgLocation<'tcx>,
+        next: PcgLocation<'tcx>,
+        phases: PhasePair,
+    ) -> MutantStream<'a, 'mir, 'tcx> {
+        let tcx = ctx.tcx();
+        // E capability does not imply that the place may be assigned, e.g. for a
+        // local that is not declared `mut`
+        let places = places_with_capability(body, &curr, &next, phases, is_exclusive)
+            .into_iter()
+            .filter(|place| {
+                let mir_place = PlaceRef::from(**place).to_place(tcx);
+                is_assignable(tcx, body, mir_place)
+                    && mir_place
+                        .ty(&body.local_decls, tcx)
+                        .ty
+                        .is_sized(tcx, body.typing_env(tcx))
+            })
+            .collect();
+
+        MutantStream::new(Box::new(Iter {
+            places,
+            write: true,
+            ctx,
+            body,
+            curr,
+        }))
+    }
+    fn name(&self) -> String {
+        "write-to-exclusive".into()
+    }
+    fn expected_outcome(&self) -> ExpectedOutcome {
+        ExpectedOutcome::Pass
+    }
+}
@@ -0,0 +1,98 @@
+use super::utils::borrowed_places;
+use super::utils::has_named_local;
+use super::utils::is_mut;
+
+use std::collections::HashSet;
+
+use super::mutator_impl::Mutant;
+use super::mutator_impl::MutantBuilder;
+use super::mutator_impl::MutantIterator;
+use super::mutator_impl::MutantStream;
+use super::mutator_impl::Mutation;
+use super::mutator_impl::PhasePair;
+
+use crate::rustc_interface::middle::mir::Body;
+use crate::rustc_interface::middle::mir::PlaceRef;
+
+use pcg::free_pcs::PcgLocation;
+use pcg::utils::CompilerCtxt;
+use pcg::utils::Place;
+
+// `ReadFromMutablyLent` creates mutants which read from a place behind a mutable borrow
+pub struct ReadFromMutablyLent;
+
+struct Iter<'a, 'tcx: 'a> {
+    mutably_lent: Vec<Place<'tcx>>,
+    ctx: CompilerCtxt<'a, 'tcx>,
+    body: &'a Body<'tcx>,
+    curr: PcgLocation<'tcx>,
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> Iter<'a, 'tcx> {
+    fn maybe_next(&mut self) -> Option<Mutant<'tcx>> {
+        let place = self.mutably_lent.pop()?;
+        let lent_place = PlaceRef::from(*place).to_place(self.ctx.tcx());
+        let mut builder = MutantBuilder::new(self.ctx.tcx(), self.body);
+
+        // Read `lent_place` into a fresh local between `curr` and `next`
+        let (_, new_read) = builder.fresh_read_local(lent_place);
+        builder.insert_after(self.curr.location, new_read)?;
+
+        builder.build(format!("{:?} was mutably lent", lent_place))
+    }
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> MutantIterator<'a, 'mir, 'tcx> for Iter<'a, 'tcx> {
+    fn next(&mut self) -> Option<Mutant<'tcx>> {
+        while !self.mutably_lent.is_empty() {
+            if let Some(mutant) = self.maybe_next() {
+                return Some(mutant);
+            }
+        }
+        return None;
+    }
+}
+
+impl Mutation for ReadFromMutablyLent {
+    fn make_stream<'a, 'mir: 'a, 'tcx: 'mir>(
+        &self,
+        ctx: CompilerCtxt<'a, 'tcx>,
+        body: &'a Body<'tcx>,
+        curr: PcgLocation<'tcx>,
+        next: PcgLocation<'tcx>,
+        phases: PhasePair,
+    ) -> MutantStream<'a, 'mir, 'tcx> {
+        let mutably_lent_in_curr = {
+            let borrows_graph = curr.states[phases.curr].borrow_pcg().graph();
+            borrowed_places(borrows_graph, is_mut)
+                .map(|(place, _)| place)
+                .collect::<HashSet<_>>()
+        };
+
+        let mutably_lent_in_next = {
+            let borrows_graph = next.states[phases.next]
+                .borrow_pcg()
+                .graph();
+            borrowed_places(borrows_graph, is_mut)
+                .map(|(place, _)| place)
+                .collect::<HashSet<_>>()
+        };
+
+        let mutably_lent = mutably_lent_in_curr
+            .iter()
+            .filter(|place| mutably_lent_in_next.contains(place))
+            .filter(|place| has_named_local(**place, body))
+            .map(|place| *place)
+            .collect();
+
+        MutantStream::new(Box::new(Iter {
+            mutably_lent,
+            ctx,
+            body,
+            curr,
+        }))
+    }
+    fn name(&self) -> String {
+        "read-from-mutably-lent".into()
+    }
+}
@@ -0,0 +1,167 @@
+use super::utils::bogus_operand;
+use super::utils::borrowed_places;
+use super::utils::has_named_local;
+use super::utils::is_shared;
+
+use std::collections::HashSet;
+
+use super::mutator_impl::Mutant;
+use super::mutator_impl::MutantBuilder;
+use super::mutator_impl::MutantIterator;
+use super::mutator_impl::MutantStream;
+use super::mutator_impl::Mutation;
+use super::mutator_impl::PhasePair;
+
+use crate::rustc_interface::middle::mir::Body;
+use crate::rustc_interface::middle::mir::BorrowKind;
+use crate::rustc_interface::middle::mir::Mutability;
+use crate::rustc_interface::middle::mir::MutBorrowKind;
+use crate::rustc_interface::middle::mir::Place as MirPlace;
+use crate::rustc_interface::middle::mir::PlaceRef;
+use crate::rustc_interface::middle::mir::ProjectionElem;
+use crate::rustc_interface::middle::mir::Rvalue;
+use crate::rustc_interface::middle::mir::StatementKind;
+
+use pcg::free_pcs::PcgLocation;
+use pcg::utils::CompilerCtxt;
+
+// Returns every place reached by dereferencing a mutable reference which is
+// shared-reborrowed at `next`, e.g. `*r` while `&*r` is live
+fn shared_reborrowed_places<'tcx>(
+    ctx: CompilerCtxt<'_, 'tcx>,
+    body: &Body<'tcx>,
+    next: &PcgLocation<'tcx>,
+    phases: PhasePair,
+) -> Vec<MirPlace<'tcx>> {
+    let tcx = ctx.tcx();
+    let borrows_graph = next.states[phases.next]
+        .borrow_pcg()
+        .graph();
+    borrowed_places(borrows_graph, is_shared)
+        .filter(|(place, _)| has_named_local(*place, body))
+        .map(|(place, _)| PlaceRef::from(*place).to_place(tcx))
+        .filter(|place| {
+            place.iter_projections().any(|(base, elem)| {
+                elem == ProjectionElem::Deref
+                    && base.ty(&body.local_decls, tcx).ty.ref_mutability() == Some(Mutability::Mut)
+            })
+        })
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+enum ReborrowAccess {
+    MutBorrow,
+    Write,
+}
+
+struct Iter<'a, 'tcx: 'a> {
+    reborrowed: Vec<MirPlace<'tcx>>,
+    access: ReborrowAccess,
+    ctx: CompilerCtxt<'a, 'tcx>,
+    body: &'a Body<'tcx>,
+    curr: PcgLocation<'tcx>,
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> MutantIterator<'a, 'mir, 'tcx> for Iter<'a, 'tcx> {
+    fn next(&mut self) -> Option<Mutant<'tcx>> {
+        while !self.reborrowed.is_empty() {
+            if let Some(mutant) = self.maybe_next() {
+                return Some(mutant);
+            }
+        }
+        return None;
+    }
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> Iter<'a, 'tcx> {
+    fn maybe_next(&mut self) -> Option<Mutant<'tcx>> {
+        let tcx = self.ctx.tcx();
+        let reborrowed_place = self.reborrowed.pop()?;
+        let mut builder = MutantBuilder::new(tcx, self.body);
+
+        // Statement which mutably reborrows `reborrowed_place` or writes to it
+        let new_access = match self.access {
+            ReborrowAccess::MutBorrow => {
+                let default_mut_borrow = BorrowKind::Mut {
+                    kind: MutBorrowKind::Default,
+                };
+                builder.fresh_ref_local(reborrowed_place, default_mut_borrow).1
+            }
+            ReborrowAccess::Write => {
+                let new_value = bogus_operand(tcx, self.body, reborrowed_place)?;
+                StatementKind::Assign(Box::new((reborrowed_place, Rvalue::Use(new_value))))
+            }
+        };
+
+        // Insert `new_access` between `curr` and `next`
+        builder.insert_after(self.curr.location, new_access)?;
+
+        builder.build(format!("{:?} was shared-reborrowed", reborrowed_place))
+    }
+}
+
+// `MutablyReborrowShared` creates mutants which mutably reborrow `*r` for a
+// mutable reference `r` while `&*r` is live
+pub struct MutablyReborrowShared;
+
+impl Mutation for MutablyReborrowShared {
+    fn make_stream<'a, 'mir: 'a, 'tcx: 'mir>(
+        &self,
+        ctx: CompilerCtxt<'a, 'tcx>,
+        body: &'a Body<'tcx>,
+        curr: PcgLocation<'tcx>,
+        next: PcgLocation<'tcx>,
+        phases: PhasePair,
+    ) -> MutantStream<'a, 'mir, 'tcx> {
+        MutantStream::new(Box::new(Iter {
+            reborrowed: shared_reborrowed_places(ctx, body, &next, phases),
+            access: ReborrowAccess::MutBorrow,
+            ctx,
+            body,
+            curr,
+        }))
+    }
+    fn name(&self) -> String {
+        "mutably-reborrow-shared".into()
+    }
+}
+
+// `WriteThroughSharedReborrow` creates mutants which write to `*r` for a
+// mutable reference `r` while `&*r` is live
+pub struct WriteThroughSharedReborrow;
+
+impl Mutation for WriteThroughSharedReborrow {
+    fn make_stream<'a, 'mir: 'a, 'tcx: 'mir>(
+        &self,
+        ctx: CompilerCtxt<'a, 'tcx>,
+        body: &'a Body<'tcx>,
+        curr: PcgLocation<'tcx>,
+        next: PcgLocation<'tcx>,
+        phases: PhasePair,
+    ) -> MutantStream<'a, 'mir, 'tcx> {
+        let tcx = ctx.tcx();
+        // Unsized values, e.g. behind a `&mut str`, cannot be assigned
+        let reborrowed = shared_reborrowed_places(ctx, body, &next, phases)
+            .into_iter()
+            .filter(|place| {
+                place
+                    .ty(&body.local_decls, tcx)
+                    .ty
+                    .is_sized(tcx, body.typing_env(tcx))
+            })
+            .collect();
+
+        MutantStream::new(Box::new(Iter {
+            reborrowed,
+            access: ReborrowAccess::Write,
+            ctx,
+            body,
+            curr,
+        }))
+    }
+    fn name(&self) -> String {
+        "write-through-shared-reborrow".into()
+    }
+}
@@ -0,0 +1,155 @@
+use super::utils::has_named_local;
+use super::utils::is_assignable;
+use super::utils::region_projection_to_current_place;
+
+use std::collections::HashSet;
+
+use super::mutator_impl::Mutant;
+use super::mutator_impl::MutantBuilder;
+use super::mutator_impl::MutantIterator;
+use super::mutator_impl::MutantStream;
+use super::mutator_impl::Mutation;
+use super::mutator_impl::PhasePair;
+
+use crate::rustc_interface::middle::mir::Body;
+use crate::rustc_interface::middle::mir::BorrowKind;
+use crate::rustc_interface::middle::mir::FakeReadCause;
+use crate::rustc_interface::middle::mir::Mutability;
+use crate::rustc_interface::middle::mir::MutBorrowKind;
+use crate::rustc_interface::middle::mir::PlaceRef;
+use crate::rustc_interface::middle::mir::StatementKind;
+use crate::rustc_interface::middle::ty::GenericArgKind;
+use crate::rustc_interface::middle::ty::Ty;
+
+use pcg::borrow_pcg::edge_data::EdgeData;
+use pcg::free_pcs::PcgLocation;
+use pcg::utils::CompilerCtxt;
+use pcg::utils::Place;
+
+// Returns true if a mutable reference appears anywhere in `ty`. Data derived from
+// the lifetime of such a reference is exclusive, so the place holding it cannot
+// be used while that data is live.
+fn contains_mut_ref<'tcx>(ty: Ty<'tcx>) -> bool {
+    ty.walk().any(|arg| match arg.unpack() {
+        GenericArgKind::Type(ty) => ty.ref_mutability() == Some(Mutability::Mut),
+        _ => false,
+    })
+}
+
+struct Iter<'a, 'tcx: 'a> {
+    // Pairs (p, q) where a region projection of p is blocked by a region projection of q
+    projection_pairs: Vec<(Place<'tcx>, Place<'tcx>)>,
+    ctx: CompilerCtxt<'a, 'tcx>,
+    body: &'a Body<'tcx>,
+    curr: PcgLocation<'tcx>,
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> MutantIterator<'a, 'mir, 'tcx> for Iter<'a, 'tcx> {
+    fn next(&mut self) -> Option<Mutant<'tcx>> {
+        while !self.projection_pairs.is_empty() {
+            if let Some(mutant) = self.maybe_next() {
+                return Some(mutant);
+            }
+        }
+        return None;
+    }
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> Iter<'a, 'tcx> {
+    fn maybe_next(&mut self) -> Option<Mutant<'tcx>> {
+        let tcx = self.ctx.tcx();
+        let (source, target) = self.projection_pairs.pop()?;
+        let source_place = PlaceRef::from(*source).to_place(tcx);
+        let target_place = PlaceRef::from(*target).to_place(tcx);
+        let mut builder = MutantBuilder::new(tcx, self.body);
+
+        let default_mut_borrow = BorrowKind::Mut {
+            kind: MutBorrowKind::Default,
+        };
+        let (_, new_borrow) = builder.fresh_ref_local(source_place, default_mut_borrow);
+
+        // Insert `new_borrow` between `curr` and `next`
+        let borrow_location = builder.insert_after(self.curr.location, new_borrow)?;
+
+        // Use `target_place` so that the borrow it holds is live at `new_borrow`
+        builder.insert_after(
+            borrow_location,
+            StatementKind::FakeRead(Box::new((FakeReadCause::ForLet(None), target_place))),
+        )?;
+
+        builder.build(format!(
+            "a region projection of {:?} was blocked by a region projection of {:?}",
+            source_place, target
+        ))
+    }
+}
+
+// `ConflictWithRegionProjection` follows edges between region projections in the
+// borrows graph. For an edge where a projection of p is blocked by a projection of
+// q, it mutably borrows p while q, whose value is derived from p's lifetime, is live.
+pub struct ConflictWithRegionProjection;
+
+impl Mutation for ConflictWithRegionProjection {
+    fn make_stream<'a, 'mir: 'a, 'tcx: 'mir>(
+        &self,
+        ctx: CompilerCtxt<'a, 'tcx>,
+        body: &'a Body<'tcx>,
+        curr: PcgLocation<'tcx>,
+        next: PcgLocation<'tcx>,
+        phases: PhasePair,
+    ) -> MutantStream<'a, 'mir, 'tcx> {
+        let borrows_graph = next.states[phases.next]
+            .borrow_pcg()
+            .graph();
+
+        let mut projection_pairs: HashSet<(Place<'tcx>, Place<'tcx>)> = HashSet::new();
+        for edge_ref in borrows_graph.edges() {
+            let sources: Vec<Place<'tcx>> = edge_ref
+                .blocked_nodes(ctx)
+                .flat_map(|node| node.try_to_local_node(ctx))
+                .flat_map(region_projection_to_current_place)
+                .collect();
+            let targets: Vec<Place<'tcx>> = edge_ref
+                .blocked_by_nodes(ctx)
+                .flat_map(region_projection_to_current_place)
+                .collect();
+            for source in sources.iter() {
+                for target in targets.iter() {
+                    if source.local != target.local {
+                        projection_pairs.insert((*source, *target));
+                    }
+                }
+            }
+        }
+
+        // The source must hold a mutable reference whose lifetime the target
+        // depends on, and must be assignable so that it can be mutably borrowed. The
+        // target must still be owned at `next`, so that it can be read after the borrow.
+        let tcx = ctx.tcx();
+        let live_in_next: HashSet<Place<'tcx>> = next.states[phases.next]
+            .capabilities()
+            .iter()
+            .map(|(place, _)| place)
+            .collect();
+        let projection_pairs = projection_pairs
+            .into_iter()
+            .filter(|(source, target)| {
+                has_named_local(*source, body)
+                    && has_named_local(*target, body)
+                    && live_in_next.contains(target)
+                    && contains_mut_ref(source.ty(ctx).ty)
+                    && is_assignable(tcx, body, PlaceRef::from(*source).to_place(tcx))
+            })
+            .collect();
+
+        MutantStream::new(Box::new(Iter {
+            projection_pairs,
+            ctx,
+            body,
+            curr,
+        }))
+    }
+    fn name(&self) -> String {
+        "conflict-with-region-projection".into()
+    }
+}
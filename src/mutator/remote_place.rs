@@ -0,0 +1,190 @@
+use super::utils::bogus_operand;
+use super::utils::borrowed_places;
+use super::utils::places_overlap;
+use super::utils::remote_places;
+
+use std::collections::HashMap;
+
+use super::mutator_impl::Mutant;
+use super::mutator_impl::MutantBuilder;
+use super::mutator_impl::MutantIterator;
+use super::mutator_impl::MutantStream;
+use super::mutator_impl::Mutation;
+use super::mutator_impl::PhasePair;
+
+use crate::ExpectedOutcome;
+
+use crate::rustc_interface::middle::mir::Body;
+use crate::rustc_interface::middle::mir::Mutability;
+use crate::rustc_interface::middle::mir::Place as MirPlace;
+use crate::rustc_interface::middle::mir::PlaceRef;
+use crate::rustc_interface::middle::mir::Rvalue;
+use crate::rustc_interface::middle::mir::StatementKind;
+
+use pcg::free_pcs::CapabilityKind;
+use pcg::free_pcs::PcgLocation;
+use pcg::utils::CompilerCtxt;
+
+// Returns the remote place `*arg` of every argument `arg` which is reborrowed,
+// i.e. blocked in the borrows graph at `next`, and whose reference has mutability `mutability`
+fn reborrowed_remote_places<'tcx>(
+    ctx: CompilerCtxt<'_, 'tcx>,
+    body: &Body<'tcx>,
+    next: &PcgLocation<'tcx>,
+    phases: PhasePair,
+    mutability: Mutability,
+) -> Vec<MirPlace<'tcx>> {
+    let borrows_graph = next.states[phases.next]
+        .borrow_pcg()
+        .graph();
+    remote_places(borrows_graph, ctx)
+        .into_iter()
+        .map(|place| PlaceRef::from(*place).to_place(ctx.tcx()))
+        .filter(|place| body.local_decls[place.local].ty.ref_mutability() == Some(mutability))
+        .collect()
+}
+
+struct Iter<'a, 'tcx: 'a> {
+    remote: Vec<MirPlace<'tcx>>,
+    ctx: CompilerCtxt<'a, 'tcx>,
+    body: &'a Body<'tcx>,
+    curr: PcgLocation<'tcx>,
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> MutantIterator<'a, 'mir, 'tcx> for Iter<'a, 'tcx> {
+    fn next(&mut self) -> Option<Mutant<'tcx>> {
+        while !self.remote.is_empty() {
+            if let Some(mutant) = self.maybe_next() {
+                return Some(mutant);
+            }
+        }
+        return None;
+    }
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> Iter<'a, 'tcx> {
+    fn maybe_next(&mut self) -> Option<Mutant<'tcx>> {
+        let remote_place = self.remote.pop()?;
+        let mut builder = MutantBuilder::new(self.ctx.tcx(), self.body);
+
+        // Statement which writes to `remote_place`
+        let new_value = bogus_operand(self.ctx.tcx(), self.body, remote_place)?;
+        let new_access = StatementKind::Assign(Box::new((remote_place, Rvalue::Use(new_value))));
+
+        // Insert `new_access` between `curr` and `next`
+        builder.insert_after(self.curr.location, new_access)?;
+
+        builder.build(format!("{:?} is provided by an argument", remote_place))
+    }
+}
+
+// `WriteThroughSharedArgument` creates mutants which write through a shared
+// reference provided as an argument
+pub struct WriteThroughSharedArgument;
+
+impl Mutation for WriteThroughSharedArgument {
+    fn make_stream<'a, 'mir: 'a, 'tcx: 'mir>(
+        &self,
+        ctx: CompilerCtxt<'a, 'tcx>,
+        body: &'a Body<'tcx>,
+        curr: PcgLocation<'tcx>,
+        next: PcgLocation<'tcx>,
+        phases: PhasePair,
+    ) -> MutantStream<'a, 'mir, 'tcx> {
+        let tcx = ctx.tcx();
+        // Unsized values, e.g. behind a `&str`, cannot be assigned
+        let remote = reborrowed_remote_places(ctx, body, &next, phases, Mutability::Not)
+            .into_iter()
+            .filter(|place| {
+                place
+                    .ty(&body.local_decls, tcx)
+                    .ty
+                    .is_sized(tcx, body.typing_env(tcx))
+            })
+            .collect();
+
+        MutantStream::new(Box::new(Iter {
+            remote,
+            ctx,
+            body,
+            curr,
+        }))
+    }
+    fn name(&self) -> String {
+        "write-through-shared-argument".into()
+    }
+}
+
+// `WriteThroughMutArgument` creates mutants which write through a mutable
+// reference provided as an argument while the place it points to is not
+// reborrowed. The borrow checker should accept all of them.
+pub struct WriteThroughMutArgument;
+
+impl Mutation for WriteThroughMutArgument {
+    fn make_stream<'a, 'mir: 'a, 'tcx: 'mir>(
+        &self,
+        ctx: CompilerCtxt<'a, 'tcx>,
+        body: &'a Body<'tcx>,
+        curr: PcgLocation<'tcx>,
+        next: PcgLocation<'tcx>,
+        phases: PhasePair,
+    ) -> MutantStream<'a, 'mir, 'tcx> {
+        let tcx = ctx.tcx();
+        let reborrowed = reborrowed_remote_places(ctx, body, &next, phases, Mutability::Mut);
+        // Reborrows of a part of the pointee, e.g. `&mut (*arg).f`, are borrows of local
+        // places rather than remote places
+        let borrowed: Vec<_> = [
+            curr.states[phases.curr].borrow_pcg().graph(),
+            next.states[phases.next].borrow_pcg().graph(),
+        ]
+        .into_iter()
+        .flat_map(|graph| borrowed_places(graph, |_| true).map(|(place, _)| place))
+        .collect();
+
+        // The argument itself must be exclusively owned at both `curr` and `next`
+        let exclusive_in_next: HashMap<_, _> = next.states[phases.next]
+            .capabilities()
+            .iter()
+            .filter(|(_, ck)| matches!(ck, CapabilityKind::Exclusive))
+            .collect();
+        let remote = curr.states[phases.curr]
+            .capabilities()
+            .iter()
+            .filter(|(_, ck)| matches!(ck, CapabilityKind::Exclusive))
+            .map(|(place, _)| place)
+            .filter(|place| exclusive_in_next.contains_key(place))
+            .filter(|place| {
+                place.projection.is_empty() && body.args_iter().any(|arg| arg == place.local)
+            })
+            .filter(|place| {
+                body.local_decls[place.local].ty.ref_mutability() == Some(Mutability::Mut)
+            })
+            .filter(|place| {
+                !borrowed
+                    .iter()
+                    .any(|borrowed_place| places_overlap(*place, *borrowed_place))
+            })
+            .map(|place| tcx.mk_place_deref(MirPlace::from(place.local)))
+            .filter(|place| !reborrowed.contains(place))
+            .filter(|place| {
+                place
+                    .ty(&body.local_decls, tcx)
+                    .ty
+                    .is_sized(tcx, body.typing_env(tcx))
+            })
+            .collect();
+
+        MutantStream::new(Box::new(Iter {
+            remote,
+            ctx,
+            body,
+            curr,
+        }))
+    }
+    fn name(&self) -> String {
+        "write-through-mut-argument".into()
+    }
+    fn expected_outcome(&self) -> ExpectedOutcome {
+        ExpectedOutcome::Pass
+    }
+}
@@ -0,0 +1,176 @@
+use super::utils::borrowed_places;
+use super::utils::has_named_local;
+use super::utils::is_mut;
+use super::utils::places_overlap;
+
+use std::collections::HashSet;
+
+use super::mutator_impl::Mutant;
+use super::mutator_impl::MutantBuilder;
+use super::mutator_impl::MutantIterator;
+use super::mutator_impl::MutantStream;
+use super::mutator_impl::Mutation;
+use super::mutator_impl::PhasePair;
+
+use crate::ExpectedOutcome;
+
+use crate::rustc_interface::middle::mir::Body;
+use crate::rustc_interface::middle::mir::BorrowKind;
+use crate::rustc_interface::middle::mir::FakeReadCause;
+use crate::rustc_interface::middle::mir::MutBorrowKind;
+use crate::rustc_interface::middle::mir::Place as MirPlace;
+use crate::rustc_interface::middle::mir::PlaceRef;
+use crate::rustc_interface::middle::mir::ProjectionElem;
+use crate::rustc_interface::middle::mir::StatementKind;
+
+use pcg::free_pcs::CapabilityKind;
+use pcg::free_pcs::PcgLocation;
+use pcg::utils::CompilerCtxt;
+use pcg::utils::Place;
+
+// Returns the place `place` is a field of, if any
+fn field_parent<'tcx>(place: Place<'tcx>) -> Option<PlaceRef<'tcx>> {
+    match place.iter_projections().last()? {
+        (parent, ProjectionElem::Field(..)) => Some(parent),
+        _ => None,
+    }
+}
+
+// `MutablyBorrowSibling` creates mutants which mutably borrow a field of a
+// struct while a different field of it is mutably lent. The borrow checker
+// should accept all of them.
+pub struct MutablyBorrowSibling;
+
+struct Iter<'a, 'tcx: 'a> {
+    // Pairs of a mutably lent field and a disjoint sibling of it
+    siblings: Vec<(Place<'tcx>, Place<'tcx>)>,
+    ctx: CompilerCtxt<'a, 'tcx>,
+    body: &'a Body<'tcx>,
+    curr: PcgLocation<'tcx>,
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> MutantIterator<'a, 'mir, 'tcx> for Iter<'a, 'tcx> {
+    fn next(&mut self) -> Option<Mutant<'tcx>> {
+        while !self.siblings.is_empty() {
+            if let Some(mutant) = self.maybe_next() {
+                return Some(mutant);
+            }
+        }
+        return None;
+    }
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> Iter<'a, 'tcx> {
+    fn maybe_next(&mut self) -> Option<Mutant<'tcx>> {
+        let (lent_place, sibling) = self.siblings.pop()?;
+        let lent_place = PlaceRef::from(*lent_place).to_place(self.ctx.tcx());
+        let sibling = PlaceRef::from(*sibling).to_place(self.ctx.tcx());
+        let mut builder = MutantBuilder::new(self.ctx.tcx(), self.body);
+
+        let default_mut_borrow = BorrowKind::Mut {
+            kind: MutBorrowKind::Default,
+        };
+
+        // Mutably borrow `sibling` into a `fresh_local` between `curr` and `next`
+        let (fresh_local, new_borrow) = builder.fresh_ref_local(sibling, default_mut_borrow);
+        let borrow_loc = builder.insert_after(self.curr.location, new_borrow)?;
+
+        // Use `fresh_local` so that the new borrow is live
+        builder.insert_after(
+            borrow_loc,
+            StatementKind::FakeRead(Box::new((
+                FakeReadCause::ForLet(None),
+                MirPlace::from(fresh_local),
+            ))),
+        )?;
+
+        builder.build(format!("{:?} was mutably lent", lent_place))
+    }
+}
+
+impl Mutation for MutablyBorrowSibling {
+    fn make_stream<'a, 'mir: 'a, 'tcx: 'mir>(
+        &self,
+        ctx: CompilerCtxt<'a, 'tcx>,
+        body: &'a Body<'tcx>,
+        curr: PcgLocation<'tcx>,
+        next: PcgLocation<'tcx>,
+        phases: PhasePair,
+    ) -> MutantStream<'a, 'mir, 'tcx> {
+        let mutably_lent_in_curr = {
+            let borrows_graph = curr.states[phases.curr].borrow_pcg().graph();
+            borrowed_places(borrows_graph, is_mut)
+                .map(|(place, _)| place)
+                .collect::<HashSet<_>>()
+        };
+
+        let (mutably_lent_in_next, lent_in_next) = {
+            let borrows_graph = next.states[phases.next]
+                .borrow_pcg()
+                .graph();
+            (
+                borrowed_places(borrows_graph, is_mut)
+                    .map(|(place, _)| place)
+                    .collect::<HashSet<_>>(),
+                borrowed_places(borrows_graph, |_| true)
+                    .map(|(place, _)| place)
+                    .collect::<Vec<_>>(),
+            )
+        };
+
+        // Fields of structs that are exclusively owned at both `curr` and `next` and
+        // disjoint from every borrowed place. Fields of a union overlap each other.
+        let tcx = ctx.tcx();
+        let exclusive_in_next: HashSet<_> = next.states[phases.next]
+            .capabilities()
+            .iter()
+            .filter(|(_, ck)| matches!(ck, CapabilityKind::Exclusive))
+            .map(|(place, _)| place)
+            .collect();
+        let expanded_fields: Vec<_> = curr.states[phases.curr]
+            .capabilities()
+            .iter()
+            .filter(|(_, ck)| matches!(ck, CapabilityKind::Exclusive))
+            .map(|(place, _)| place)
+            .filter(|place| exclusive_in_next.contains(place))
+            .filter(|place| {
+                field_parent(*place).is_some_and(|parent| !parent.ty(body, tcx).ty.is_union())
+            })
+            .filter(|place| {
+                !lent_in_next
+                    .iter()
+                    .any(|lent_place| places_overlap(*place, *lent_place))
+            })
+            .collect();
+
+        let siblings = mutably_lent_in_curr
+            .iter()
+            .filter(|place| mutably_lent_in_next.contains(place))
+            .filter(|place| has_named_local(**place, body))
+            .flat_map(|lent_place| {
+                let parent = field_parent(*lent_place);
+                expanded_fields
+                    .iter()
+                    .filter(move |sibling| {
+                        parent.is_some()
+                            && **sibling != *lent_place
+                            && field_parent(**sibling) == parent
+                    })
+                    .map(move |sibling| (*lent_place, *sibling))
+            })
+            .collect();
+
+        MutantStream::new(Box::new(Iter {
+            siblings,
+            ctx,
+            body,
+            curr,
+        }))
+    }
+    fn name(&self) -> String {
+        "mutably-borrow-sibling".into()
+    }
+    fn expected_outcome(&self) -> ExpectedOutcome {
+        ExpectedOutcome::Pass
+    }
+}
@@ -0,0 +1,237 @@
+use super::utils::borrowed_places;
+use super::utils::borrowed_places_with_blockers;
+use super::utils::has_named_local;
+use super::utils::is_mut;
+use super::utils::places_overlap;
+
+use std::collections::HashSet;
+
+use super::mutator_impl::Mutant;
+use super::mutator_impl::MutantBuilder;
+use super::mutator_impl::MutantIterator;
+use super::mutator_impl::MutantStream;
+use super::mutator_impl::Mutation;
+use super::mutator_impl::PhasePair;
+
+use crate::rustc_interface::middle::mir::Body;
+use crate::rustc_interface::middle::mir::BorrowKind;
+use crate::rustc_interface::middle::mir::Location;
+use crate::rustc_interface::middle::mir::Operand;
+use crate::rustc_interface::middle::mir::Place as MirPlace;
+use crate::rustc_interface::middle::mir::Rvalue;
+use crate::rustc_interface::middle::mir::Statement;
+use crate::rustc_interface::middle::mir::StatementKind;
+
+use pcg::free_pcs::PcgLocation;
+use pcg::utils::CompilerCtxt;
+use pcg::utils::Place;
+
+// Returns the places `statement` accesses, along with whether each access
+// writes to (or moves out of, or mutably borrows) the place
+fn statement_accesses<'tcx>(statement: &Statement<'tcx>) -> Vec<(MirPlace<'tcx>, bool)> {
+    let mut accesses = vec![];
+    if let StatementKind::Assign(assign) = &statement.kind {
+        accesses.push((assign.0, true));
+        match &assign.1 {
+            Rvalue::Ref(_, BorrowKind::Shared, place) => accesses.push((*place, false)),
+            Rvalue::Ref(_, _, place) => accesses.push((*place, true)),
+            Rvalue::Use(Operand::Copy(place)) => accesses.push((*place, false)),
+            Rvalue::Use(Operand::Move(place)) => accesses.push((*place, true)),
+            _ => {}
+        }
+    }
+    accesses
+}
+
+// Returns the places whose values `statement` uses: the operands of an assignment,
+// and its destination if that is reached through a reference
+fn statement_uses<'tcx>(statement: &Statement<'tcx>) -> Vec<MirPlace<'tcx>> {
+    match &statement.kind {
+        StatementKind::Assign(assign) => {
+            // The first access is the destination
+            let mut uses: Vec<_> = statement_accesses(statement)
+                .into_iter()
+                .skip(1)
+                .map(|(place, _)| place)
+                .collect();
+            if assign.0.is_indirect() {
+                uses.push(assign.0);
+            }
+            uses
+        }
+        _ => vec![],
+    }
+}
+
+// Returns true if `statement` accesses a place that overlaps one of `accesses`, or
+// ends or begins the storage of one of their locals. Moving a statement past one
+// that depends on it only produces unrelated errors, such as uses of
+// uninitialized or dead locals.
+fn depends_on<'tcx>(statement: &Statement<'tcx>, accesses: &[(MirPlace<'tcx>, bool)]) -> bool {
+    match &statement.kind {
+        StatementKind::StorageLive(local) | StatementKind::StorageDead(local) => {
+            accesses.iter().any(|(place, _)| place.local == *local)
+        }
+        _ => statement_accesses(statement).iter().any(|(accessed_place, _)| {
+            accesses
+                .iter()
+                .any(|(place, _)| places_overlap((*place).into(), (*accessed_place).into()))
+        }),
+    }
+}
+
+// Moving `curr` right after the statement at `target_index` in its block, because
+// that statement accesses `accessed_place`, which conflicts with `released_place`
+struct MoveLater<'tcx> {
+    target_index: usize,
+    released_place: Place<'tcx>,
+    accessed_place: MirPlace<'tcx>,
+}
+
+struct Iter<'a, 'tcx: 'a> {
+    moves: Vec<MoveLater<'tcx>>,
+    ctx: CompilerCtxt<'a, 'tcx>,
+    body: &'a Body<'tcx>,
+    curr: PcgLocation<'tcx>,
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> MutantIterator<'a, 'mir, 'tcx> for Iter<'a, 'tcx> {
+    fn next(&mut self) -> Option<Mutant<'tcx>> {
+        while !self.moves.is_empty() {
+            if let Some(mutant) = self.maybe_next() {
+                return Some(mutant);
+            }
+        }
+        return None;
+    }
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> Iter<'a, 'tcx> {
+    fn maybe_next(&mut self) -> Option<Mutant<'tcx>> {
+        let MoveLater {
+            target_index,
+            released_place,
+            accessed_place,
+        } = self.moves.pop()?;
+        let mut builder = MutantBuilder::new(self.ctx.tcx(), self.body);
+
+        // Move `curr` after the conflicting access
+        let target = Location {
+            block: self.curr.location.block,
+            statement_index: target_index,
+        };
+        builder.move_after(self.curr.location, target)?;
+
+        builder.build(format!(
+            "{:?} was released before an access to {:?}",
+            released_place, accessed_place
+        ))
+    }
+}
+
+// `SwapPastRelease` moves a statement later in its block when a borrow blocking
+// some place expires right after it and a later statement accesses that place in
+// a way the borrow forbids. Moving it right after the next statement swaps the
+// two. After the move the access happens before the last use of the borrow, like
+// moving the last use of a reference past a write to the borrowed place.
+pub struct SwapPastRelease;
+
+impl Mutation for SwapPastRelease {
+    fn make_stream<'a, 'mir: 'a, 'tcx: 'mir>(
+        &self,
+        ctx: CompilerCtxt<'a, 'tcx>,
+        body: &'a Body<'tcx>,
+        curr: PcgLocation<'tcx>,
+        next: PcgLocation<'tcx>,
+        phases: PhasePair,
+    ) -> MutantStream<'a, 'mir, 'tcx> {
+        let lent_in_next: HashSet<Place<'tcx>> = {
+            let borrows_graph = next.states[phases.next]
+                .borrow_pcg()
+                .graph();
+            borrowed_places(borrows_graph, |_| true)
+                .map(|(place, _)| place)
+                .collect()
+        };
+
+        // Only statements followed by another statement in the same block are moved
+        let statements = &body.basic_blocks[curr.location.block].statements;
+        let curr_statement = if next.location == curr.location.successor_within_block() {
+            statements.get(curr.location.statement_index)
+        } else {
+            None
+        };
+        let curr_uses = curr_statement.map(statement_uses).unwrap_or_default();
+
+        // Places which are lent after `curr` but no longer lent at `next`, along
+        // with whether they were mutably lent. The release must be caused by
+        // `curr` using a place that blocks the released place, e.g. as the last
+        // use of a reference, or else swapping does not extend the borrow.
+        let released: Vec<(Place<'tcx>, bool)> = {
+            let borrows_graph = curr.states[phases.curr]
+                .borrow_pcg()
+                .graph();
+            let mutably_lent: HashSet<Place<'tcx>> = borrowed_places(borrows_graph, is_mut)
+                .map(|(place, _)| place)
+                .collect();
+            borrowed_places_with_blockers(borrows_graph, ctx, |_| true)
+                .into_iter()
+                .filter(|(place, _)| !lent_in_next.contains(place))
+                .filter(|(place, _)| has_named_local(*place, body))
+                .filter(|(_, blocking_place)| {
+                    curr_uses
+                        .iter()
+                        .any(|used_place| places_overlap(*blocking_place, (*used_place).into()))
+                })
+                .map(|(place, _)| place)
+                .collect::<HashSet<_>>()
+                .into_iter()
+                .map(|place| (place, mutably_lent.contains(&place)))
+                .collect()
+        };
+
+        // Move `curr` past each later statement with a conflicting access, as long
+        // as no statement up to that one depends on `curr`
+        let mut moves = vec![];
+        if let Some(curr_statement) = curr_statement {
+            let curr_accesses = statement_accesses(curr_statement);
+            let later_statements = statements
+                .iter()
+                .enumerate()
+                .skip(curr.location.statement_index + 1);
+            for (target_index, statement) in later_statements {
+                if depends_on(statement, &curr_accesses) {
+                    break;
+                }
+                let conflict = statement_accesses(statement)
+                    .into_iter()
+                    .find_map(|(accessed_place, is_write)| {
+                        released
+                            .iter()
+                            .find(|(released_place, mutably_lent)| {
+                                (is_write || *mutably_lent)
+                                    && places_overlap(*released_place, accessed_place.into())
+                            })
+                            .map(|(released_place, _)| (*released_place, accessed_place))
+                    });
+                if let Some((released_place, accessed_place)) = conflict {
+                    moves.push(MoveLater {
+                        target_index,
+                        released_place,
+                        accessed_place,
+                    });
+                }
+            }
+        }
+
+        MutantStream::new(Box::new(Iter {
+            moves,
+            ctx,
+            body,
+            curr,
+        }))
+    }
+    fn name(&self) -> String {
+        "swap-past-release".into()
+    }
+}
@@ -0,0 +1,98 @@
+use super::utils::borrowed_places_with_blockers;
+use super::utils::has_named_local;
+
+use std::collections::HashSet;
+
+use super::mutator_impl::Mutant;
+use super::mutator_impl::MutantBuilder;
+use super::mutator_impl::MutantIterator;
+use super::mutator_impl::MutantStream;
+use super::mutator_impl::Mutation;
+use super::mutator_impl::PhasePair;
+
+use crate::rustc_interface::middle::mir::Body;
+use crate::rustc_interface::middle::mir::FakeReadCause;
+use crate::rustc_interface::middle::mir::Local;
+use crate::rustc_interface::middle::mir::PlaceRef;
+use crate::rustc_interface::middle::mir::StatementKind;
+
+use pcg::free_pcs::PcgLocation;
+use pcg::utils::CompilerCtxt;
+use pcg::utils::Place;
+
+// `StorageDeadWhileBorrowed` creates mutants which end the storage of a local
+// while some place in it is borrowed, then use the blocking reference
+pub struct StorageDeadWhileBorrowed;
+
+struct Iter<'a, 'tcx: 'a> {
+    borrowed_locals: Vec<(Local, Place<'tcx>)>,
+    ctx: CompilerCtxt<'a, 'tcx>,
+    body: &'a Body<'tcx>,
+    curr: PcgLocation<'tcx>,
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> MutantIterator<'a, 'mir, 'tcx> for Iter<'a, 'tcx> {
+    fn next(&mut self) -> Option<Mutant<'tcx>> {
+        while !self.borrowed_locals.is_empty() {
+            if let Some(mutant) = self.maybe_next() {
+                return Some(mutant);
+            }
+        }
+        return None;
+    }
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> Iter<'a, 'tcx> {
+    fn maybe_next(&mut self) -> Option<Mutant<'tcx>> {
+        let (local, blocking_place) = self.borrowed_locals.pop()?;
+        let blocking_place = PlaceRef::from(*blocking_place).to_place(self.ctx.tcx());
+        let mut builder = MutantBuilder::new(self.ctx.tcx(), self.body);
+
+        // End the storage of `local` between `curr` and `next`
+        let storage_dead_location =
+            builder.insert_after(self.curr.location, StatementKind::StorageDead(local))?;
+
+        // Use `blocking_place` so that the borrow of `local` is live
+        builder.insert_after(
+            storage_dead_location,
+            StatementKind::FakeRead(Box::new((FakeReadCause::ForLet(None), blocking_place))),
+        )?;
+
+        builder.build(format!("{:?} was borrowed by {:?}", local, blocking_place))
+    }
+}
+
+impl Mutation for StorageDeadWhileBorrowed {
+    fn make_stream<'a, 'mir: 'a, 'tcx: 'mir>(
+        &self,
+        ctx: CompilerCtxt<'a, 'tcx>,
+        body: &'a Body<'tcx>,
+        curr: PcgLocation<'tcx>,
+        next: PcgLocation<'tcx>,
+        phases: PhasePair,
+    ) -> MutantStream<'a, 'mir, 'tcx> {
+        let borrows_graph = next.states[phases.next]
+            .borrow_pcg()
+            .graph();
+
+        // Arguments have no `StorageLive`/`StorageDead` of their own, so we skip them
+        let borrowed_locals: HashSet<(Local, Place<'tcx>)> =
+            borrowed_places_with_blockers(borrows_graph, ctx, |_| true)
+                .into_iter()
+                .filter(|(place, _)| has_named_local(*place, body))
+                .filter(|(place, _)| place.local.index() > body.arg_count)
+                .filter(|(place, blocking_place)| place.local != blocking_place.local)
+                .map(|(place, blocking_place)| (place.local, blocking_place))
+                .collect();
+
+        MutantStream::new(Box::new(Iter {
+            borrowed_locals: borrowed_locals.into_iter().collect(),
+            ctx,
+            body,
+            curr,
+        }))
+    }
+    fn name(&self) -> String {
+        "storage-dead-while-borrowed".into()
+    }
+}
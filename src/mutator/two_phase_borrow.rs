@@ -0,0 +1,277 @@
+use super::utils::borrowed_places;
+use super::utils::has_named_local;
+use super::utils::BodyCache;
+
+use std::collections::HashSet;
+
+use super::mutator_impl::Mutant;
+use super::mutator_impl::MutantBuilder;
+use super::mutator_impl::MutantIterator;
+use super::mutator_impl::MutantStream;
+use super::mutator_impl::Mutation;
+use super::mutator_impl::PhasePair;
+
+use crate::ExpectedOutcome;
+
+use crate::rustc_interface::borrowck::consumers::BorrowSet;
+use crate::rustc_interface::borrowck::consumers::TwoPhaseActivation;
+
+use crate::rustc_interface::middle::mir::BasicBlocks;
+use crate::rustc_interface::middle::mir::Body;
+use crate::rustc_interface::middle::mir::BorrowKind;
+use crate::rustc_interface::middle::mir::FakeReadCause;
+use crate::rustc_interface::middle::mir::Location;
+use crate::rustc_interface::middle::mir::MutBorrowKind;
+use crate::rustc_interface::middle::mir::Place as MirPlace;
+use crate::rustc_interface::middle::mir::PlaceRef;
+use crate::rustc_interface::middle::mir::Rvalue;
+use crate::rustc_interface::middle::mir::StatementKind;
+
+use pcg::free_pcs::PcgLocation;
+use pcg::pcg::BodyWithBorrowckFacts;
+use pcg::utils::CompilerCtxt;
+
+// Returns true if `location` lies between the reservation and the activation of a
+// two-phase borrow. This mirrors `is_active` in `borrowck/src/path_utils.rs`.
+fn in_reservation_window(
+    basic_blocks: &BasicBlocks<'_>,
+    reserve_location: Location,
+    activation_location: Location,
+    location: Location,
+) -> bool {
+    let dominators = basic_blocks.dominators();
+    !activation_location.dominates(location, dominators)
+        && reserve_location
+            .successor_within_block()
+            .dominates(location, dominators)
+}
+
+fn is_two_phase(kind: BorrowKind) -> bool {
+    matches!(
+        kind,
+        BorrowKind::Mut {
+            kind: MutBorrowKind::TwoPhaseBorrow
+        }
+    )
+}
+
+// The reservation and activation locations of every two-phase borrow in a body
+type ReservationWindows = Vec<(Location, Location)>;
+
+thread_local! {
+    // The reservation windows of the body that mutants are currently built from.
+    // They are shared by `MutateInReservation` and `ReadInReservation`.
+    static RESERVATION_WINDOWS: BodyCache<ReservationWindows> = BodyCache::default();
+}
+
+fn reservation_windows(borrow_set: &BorrowSet<'_>) -> ReservationWindows {
+    borrow_set
+        .location_map()
+        .values()
+        .filter_map(|borrow_data| match borrow_data.activation_location() {
+            TwoPhaseActivation::ActivatedAt(activation_location) => {
+                Some((borrow_data.reserve_location(), activation_location))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+// Computes the reservation windows of `body` from the `BorrowSet` that the borrow
+// checker built for it
+fn prepare_reservation_windows(body: &BodyWithBorrowckFacts<'_>) {
+    RESERVATION_WINDOWS.with(|windows| {
+        windows.with_mut(&body.body, || reservation_windows(&body.borrow_set), |_| ())
+    });
+}
+
+// Returns the places reserved at `curr`, see `reserved_places`. The windows are
+// computed by `prepare_reservation_windows` before any site of `body` is visited.
+fn reserved_places_at<'tcx>(
+    ctx: CompilerCtxt<'_, 'tcx>,
+    body: &Body<'tcx>,
+    curr: &PcgLocation<'tcx>,
+    next: &PcgLocation<'tcx>,
+    phases: PhasePair,
+) -> Vec<MirPlace<'tcx>> {
+    RESERVATION_WINDOWS.with(|windows| {
+        windows.with_mut(body, Vec::new, |windows| {
+            reserved_places(ctx, body, windows, curr, next, phases)
+        })
+    })
+}
+
+// Returns every place that is blocked by a two-phase borrow in the PCG at both
+// `curr` and `next`, and whose borrow is reserved but not yet activated right
+// after `curr`
+fn reserved_places<'tcx>(
+    ctx: CompilerCtxt<'_, 'tcx>,
+    body: &Body<'tcx>,
+    windows: &ReservationWindows,
+    curr: &PcgLocation<'tcx>,
+    next: &PcgLocation<'tcx>,
+    phases: PhasePair,
+) -> Vec<MirPlace<'tcx>> {
+    let tcx = ctx.tcx();
+    let mutant_location = curr.location.successor_within_block();
+
+    // The PCG does not distinguish reserved from activated borrows
+    let in_reservation: HashSet<MirPlace<'tcx>> = windows
+        .iter()
+        .filter(|(reserve_location, activation_location)| {
+            in_reservation_window(
+                &body.basic_blocks,
+                *reserve_location,
+                *activation_location,
+                mutant_location,
+            )
+        })
+        .flat_map(|(reserve_location, _)| {
+            let statement = body.basic_blocks[reserve_location.block]
+                .statements
+                .get(reserve_location.statement_index)?;
+            match &statement.kind {
+                StatementKind::Assign(assign) => match assign.1 {
+                    Rvalue::Ref(_, _, place) => Some(place),
+                    _ => None,
+                },
+                _ => None,
+            }
+        })
+        .collect();
+
+    let reserved_in_next: HashSet<_> = {
+        let borrows_graph = next.states[phases.next].borrow_pcg().graph();
+        borrowed_places(borrows_graph, is_two_phase)
+            .map(|(place, _)| place)
+            .collect()
+    };
+
+    let borrows_graph = curr.states[phases.curr].borrow_pcg().graph();
+    borrowed_places(borrows_graph, is_two_phase)
+        .map(|(place, _)| place)
+        .filter(|place| reserved_in_next.contains(place))
+        .filter(|place| has_named_local(*place, body))
+        .map(|place| PlaceRef::from(*place).to_place(tcx))
+        .filter(|place| in_reservation.contains(place))
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+struct Iter<'a, 'tcx: 'a> {
+    reserved: Vec<MirPlace<'tcx>>,
+    // Whether to mutably borrow `reserved` rather than read from it
+    mutate: bool,
+    ctx: CompilerCtxt<'a, 'tcx>,
+    body: &'a Body<'tcx>,
+    curr: PcgLocation<'tcx>,
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> MutantIterator<'a, 'mir, 'tcx> for Iter<'a, 'tcx> {
+    fn next(&mut self) -> Option<Mutant<'tcx>> {
+        while !self.reserved.is_empty() {
+            if let Some(mutant) = self.maybe_next() {
+                return Some(mutant);
+            }
+        }
+        return None;
+    }
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> Iter<'a, 'tcx> {
+    fn maybe_next(&mut self) -> Option<Mutant<'tcx>> {
+        let reserved_place = self.reserved.pop()?;
+        let mut builder = MutantBuilder::new(self.ctx.tcx(), self.body);
+
+        // Access `reserved_place` into a `fresh_local`
+        let (fresh_local, new_access) = if self.mutate {
+            let default_mut_borrow = BorrowKind::Mut {
+                kind: MutBorrowKind::Default,
+            };
+            builder.fresh_ref_local(reserved_place, default_mut_borrow)
+        } else {
+            builder.fresh_read_local(reserved_place)
+        };
+
+        // Insert `new_access` between `curr` and `next`
+        let access_loc = builder.insert_after(self.curr.location, new_access)?;
+
+        // Use `fresh_local` so that the new access is live
+        builder.insert_after(
+            access_loc,
+            StatementKind::FakeRead(Box::new((
+                FakeReadCause::ForLet(None),
+                MirPlace::from(fresh_local),
+            ))),
+        )?;
+
+        builder.build(format!("{:?} was reserved by a two-phase borrow", reserved_place))
+    }
+}
+
+// `MutateInReservation` creates mutants which mutably borrow a place between the
+// reservation and activation of a two-phase borrow of it
+pub struct MutateInReservation;
+
+impl Mutation for MutateInReservation {
+    fn make_stream<'a, 'mir: 'a, 'tcx: 'mir>(
+        &self,
+        ctx: CompilerCtxt<'a, 'tcx>,
+        body: &'a Body<'tcx>,
+        curr: PcgLocation<'tcx>,
+        next: PcgLocation<'tcx>,
+        phases: PhasePair,
+    ) -> MutantStream<'a, 'mir, 'tcx> {
+        let reserved = reserved_places_at(ctx, body, &curr, &next, phases);
+
+        MutantStream::new(Box::new(Iter {
+            reserved,
+            mutate: true,
+            ctx,
+            body,
+            curr,
+        }))
+    }
+    fn name(&self) -> String {
+        "mutate-in-reservation".into()
+    }
+    fn prepare(&self, body: &BodyWithBorrowckFacts<'_>) {
+        prepare_reservation_windows(body);
+    }
+}
+
+// `ReadInReservation` creates mutants which read a place between the reservation
+// and activation of a two-phase borrow of it. The borrow checker should accept
+// all of them.
+pub struct ReadInReservation;
+
+impl Mutation for ReadInReservation {
+    fn make_stream<'a, 'mir: 'a, 'tcx: 'mir>(
+        &self,
+        ctx: CompilerCtxt<'a, 'tcx>,
+        body: &'a Body<'tcx>,
+        curr: PcgLocation<'tcx>,
+        next: PcgLocation<'tcx>,
+        phases: PhasePair,
+    ) -> MutantStream<'a, 'mir, 'tcx> {
+        let reserved = reserved_places_at(ctx, body, &curr, &next, phases);
+
+        MutantStream::new(Box::new(Iter {
+            reserved,
+            mutate: false,
+            ctx,
+            body,
+            curr,
+        }))
+    }
+    fn name(&self) -> String {
+        "read-in-reservation".into()
+    }
+    fn prepare(&self, body: &BodyWithBorrowckFacts<'_>) {
+        prepare_reservation_windows(body);
+    }
+    fn expected_outcome(&self) -> ExpectedOutcome {
+        ExpectedOutcome::Pass
+    }
+}
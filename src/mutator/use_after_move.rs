@@ -0,0 +1,135 @@
+use super::utils::has_named_local;
+use super::utils::places_overlap;
+
+use std::collections::HashSet;
+
+use super::mutator_impl::Mutant;
+use super::mutator_impl::MutantBuilder;
+use super::mutator_impl::MutantIterator;
+use super::mutator_impl::MutantStream;
+use super::mutator_impl::Mutation;
+use super::mutator_impl::PhasePair;
+
+use crate::rustc_interface::middle::mir::Body;
+use crate::rustc_interface::middle::mir::PlaceRef;
+
+use pcg::free_pcs::PcgLocation;
+use pcg::pcg::EvalStmtPhase;
+use pcg::utils::CompilerCtxt;
+use pcg::utils::Place;
+
+// `UseAfterMove` creates mutants which read from places that lost all capability
+// because they were moved out
+pub struct UseAfterMove;
+
+struct Iter<'a, 'tcx: 'a> {
+    moved: Vec<Place<'tcx>>,
+    ctx: CompilerCtxt<'a, 'tcx>,
+    body: &'a Body<'tcx>,
+    next: PcgLocation<'tcx>,
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> Iter<'a, 'tcx> {
+    fn maybe_next(&mut self) -> Option<Mutant<'tcx>> {
+        let place = self.moved.pop()?;
+        let moved_place = PlaceRef::from(*place).to_place(self.ctx.tcx());
+        let mut builder = MutantBuilder::new(self.ctx.tcx(), self.body);
+
+        // Read `moved_place` into a fresh local right after `next`, whose operands
+        // moved it out. If `next` is a terminator, the read is at the start of each
+        // of its successors instead.
+        let location = self.next.location;
+        let bb = &self.body.basic_blocks[location.block];
+        if location.statement_index < bb.statements.len() {
+            let (_, new_read) = builder.fresh_read_local(moved_place);
+            builder.insert_after(location, new_read)?;
+        } else {
+            let mut successors: Vec<_> = bb
+                .terminator()
+                .successors()
+                .filter(|successor| !self.body.basic_blocks[*successor].is_cleanup)
+                .collect();
+            successors.sort();
+            successors.dedup();
+            if successors.is_empty() {
+                return None;
+            }
+            for successor in successors {
+                let (_, new_read) = builder.fresh_read_local(moved_place);
+                builder.insert_before(successor.start_location(), new_read)?;
+            }
+        }
+
+        builder.build(format!("{:?} was moved out", moved_place))
+    }
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> MutantIterator<'a, 'mir, 'tcx> for Iter<'a, 'tcx> {
+    fn next(&mut self) -> Option<Mutant<'tcx>> {
+        while !self.moved.is_empty() {
+            if let Some(mutant) = self.maybe_next() {
+                return Some(mutant);
+            }
+        }
+        return None;
+    }
+}
+
+impl Mutation for UseAfterMove {
+    fn make_stream<'a, 'mir: 'a, 'tcx: 'mir>(
+        &self,
+        ctx: CompilerCtxt<'a, 'tcx>,
+        body: &'a Body<'tcx>,
+        curr: PcgLocation<'tcx>,
+        next: PcgLocation<'tcx>,
+        phases: PhasePair,
+    ) -> MutantStream<'a, 'mir, 'tcx> {
+        let owned_in_curr: Vec<_> = curr.states[phases.curr]
+            .capabilities()
+            .iter()
+            .map(|(place, _)| place)
+            .collect();
+
+        let owned_in_next: HashSet<_> = next.states[phases.next]
+            .capabilities()
+            .iter()
+            .map(|(place, _)| place)
+            .collect();
+
+        // The read is inserted after `next`, so the place must not have been
+        // reinitialized by the main effect of `next`
+        let owned_after_next: HashSet<_> = next.states[EvalStmtPhase::PostMain]
+            .capabilities()
+            .iter()
+            .map(|(place, _)| place)
+            .collect();
+
+        // A place can also disappear from the capabilities because it was expanded
+        // or collapsed, in which case some overlapping place is still present
+        let moved = owned_in_curr
+            .iter()
+            .filter(|place| {
+                !owned_in_next
+                    .iter()
+                    .any(|next_place| places_overlap(**place, *next_place))
+            })
+            .filter(|place| {
+                !owned_after_next
+                    .iter()
+                    .any(|next_place| places_overlap(**place, *next_place))
+            })
+            .filter(|place| has_named_local(**place, body))
+            .map(|place| *place)
+            .collect();
+
+        MutantStream::new(Box::new(Iter {
+            moved,
+            ctx,
+            body,
+            next,
+        }))
+    }
+    fn name(&self) -> String {
+        "use-after-move".into()
+    }
+}
@@ -1,32 +1,58 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
+
 use pcg::borrow_pcg::borrow_pcg_edge::BorrowPcgEdgeLike;
 use pcg::borrow_pcg::borrow_pcg_edge::LocalNode;
 use pcg::borrow_pcg::edge::borrow::BorrowEdge;
 use pcg::borrow_pcg::edge::kind::BorrowPcgEdgeKind;
+use pcg::borrow_pcg::edge_data::EdgeData;
 use pcg::borrow_pcg::graph::BorrowsGraph;
 
 use pcg::utils::maybe_remote::MaybeRemotePlace;
 use pcg::utils::maybe_old::MaybeOldPlace;
 use pcg::utils::place::Place;
+use pcg::utils::CompilerCtxt;
 
+use pcg::free_pcs::PcgLocation;
 use pcg::pcg::PCGNode;
 
+use super::mutator_impl::PhasePair;
+
 use crate::rustc_interface::ast::ast::BindingMode;
 
+use crate::rustc_interface::abi::Size;
+
+use crate::rustc_interface::hir::def_id::DefId;
+
 use crate::rustc_interface::middle::ty::Region;
 use crate::rustc_interface::middle::ty::Ty;
+use crate::rustc_interface::middle::ty::TyCtxt;
+use crate::rustc_interface::middle::ty::TyKind;
 
 use crate::rustc_interface::middle::mir::BasicBlock;
 use crate::rustc_interface::middle::mir::BasicBlockData;
 use crate::rustc_interface::middle::mir::BindingForm;
 use crate::rustc_interface::middle::mir::Body;
 use crate::rustc_interface::middle::mir::BorrowKind;
+use crate::rustc_interface::middle::mir::Mutability;
 use crate::rustc_interface::middle::mir::MutBorrowKind;
 use crate::rustc_interface::middle::mir::ClearCrossCrate;
+use crate::rustc_interface::middle::mir::Const;
+use crate::rustc_interface::middle::mir::ConstOperand;
+use crate::rustc_interface::middle::mir::ConstValue;
 use crate::rustc_interface::middle::mir::Local;
 use crate::rustc_interface::middle::mir::LocalDecl;
 use crate::rustc_interface::middle::mir::LocalInfo;
+use crate::rustc_interface::middle::mir::Location;
+use crate::rustc_interface::middle::mir::Operand;
+use crate::rustc_interface::middle::mir::Place as MirPlace;
+use crate::rustc_interface::middle::mir::ProjectionElem;
 use crate::rustc_interface::middle::mir::SourceInfo;
+use crate::rustc_interface::middle::mir::Statement;
+use crate::rustc_interface::middle::mir::Terminator;
+use crate::rustc_interface::middle::mir::TerminatorKind;
 use crate::rustc_interface::middle::mir::VarBindingForm;
+use crate::rustc_interface::middle::mir::interpret::Allocation;
 
 pub(crate) fn local_node_to_current_place<'tcx>(pcg_node: LocalNode<'tcx>) -> Option<Place<'tcx>> {
     match pcg_node {
@@ -36,6 +62,15 @@ pub(crate) fn local_node_to_current_place<'tcx>(pcg_node: LocalNode<'tcx>) -> Op
     }
 }
 
+// Returns the current place whose region projection is `pcg_node`
+pub(crate) fn region_projection_to_current_place<'tcx>(pcg_node: LocalNode<'tcx>) -> Option<Place<'tcx>> {
+    match pcg_node {
+        PCGNode::RegionProjection(region_projection) =>
+            maybe_old_place_to_current_place(region_projection.place()),
+        _ => None,
+    }
+}
+
 pub(crate) fn maybe_old_place_to_current_place<'tcx>(maybe_old_place: MaybeOldPlace<'tcx>) -> Option<Place<'tcx>>{
     match maybe_old_place {
         MaybeOldPlace::Current { place } => Some(place),
@@ -43,12 +78,27 @@ pub(crate) fn maybe_old_place_to_current_place<'tcx>(maybe_old_place: MaybeOldPl
     }
 }
 
-#[allow(dead_code)]
-pub(crate) fn maybe_remote_place_to_local_place<'tcx>(maybe_remote_place: MaybeRemotePlace<'tcx>) -> Option<MaybeOldPlace<'tcx>>{
+// Returns the current place that a labelled old place is a snapshot of
+pub(crate) fn old_place_to_current_place<'tcx>(maybe_old_place: MaybeOldPlace<'tcx>) -> Option<Place<'tcx>> {
+    match maybe_old_place {
+        MaybeOldPlace::Current { .. } => None,
+        MaybeOldPlace::OldPlace(snapshot) => Some(snapshot.place),
+    }
+}
+
+// Returns the current place that `maybe_remote_place` refers to. The remote
+// place of an argument `arg` is the place `*arg`.
+pub(crate) fn maybe_remote_place_to_current_place<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    maybe_remote_place: MaybeRemotePlace<'tcx>,
+) -> Option<Place<'tcx>> {
     match maybe_remote_place {
         MaybeRemotePlace::Local(maybe_old_place) =>
-            Some(maybe_old_place),
-        MaybeRemotePlace::Remote(_) => None,
+            maybe_old_place_to_current_place(maybe_old_place),
+        MaybeRemotePlace::Remote(remote_place) => {
+            let arg = MirPlace::from(remote_place.assigned_local());
+            Some(tcx.mk_place_deref(arg).into())
+        }
     }
 }
 
@@ -73,6 +123,88 @@ pub(crate) fn fresh_basic_block<'tcx>(body: &mut Body<'tcx>) -> BasicBlock {
     body.basic_blocks_mut().push(BasicBlockData::new(None))
 }
 
+// Create an operand to overwrite `place` with, without reading `place` or any
+// other place. The operand is a constant of the type of `place`: a zero-sized value
+// if the type is zero-sized, and all zero bytes otherwise. The constant is never
+// evaluated, as mutants are only borrow checked. Returns `None` if the layout of
+// the type is unknown, e.g. because it is generic.
+pub(crate) fn bogus_operand<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+    place: MirPlace<'tcx>,
+) -> Option<Operand<'tcx>> {
+    let ty = place.ty(&body.local_decls, tcx).ty;
+    let layout = tcx
+        .layout_of(body.typing_env(tcx).as_query_input(tcx.erase_regions(ty)))
+        .ok()?;
+    let value = if layout.is_zst() {
+        ConstValue::ZeroSized
+    } else {
+        let bytes = vec![0u8; layout.size.bytes_usize()];
+        let allocation = Allocation::from_bytes(bytes, layout.align.abi, Mutability::Not);
+        let alloc_id = tcx.reserve_and_set_memory_alloc(tcx.mk_const_alloc(allocation));
+        ConstValue::Indirect {
+            alloc_id,
+            offset: Size::ZERO,
+        }
+    };
+    Some(Operand::Constant(Box::new(ConstOperand {
+        span: bogus_source_info(body).span,
+        user_ty: None,
+        const_: Const::Val(value, ty),
+    })))
+}
+
+// Split the basic block containing `location` right after `location`. The
+// original block ends in a `FalseEdge` whose real target is the rest of the
+// block and whose imaginary target is a fresh block containing `statements`
+// and ending in `terminator`. Control always flows to the real target, but
+// the compiler type-checks the body as if it could reach the fresh block.
+// Returns the index of the fresh block, or `None` if `location` is not a
+// statement (e.g. the terminator of a block without statements).
+pub(crate) fn split_with_imaginary_branch<'tcx>(
+    body: &mut Body<'tcx>,
+    location: Location,
+    statements: Vec<Statement<'tcx>>,
+    terminator: TerminatorKind<'tcx>,
+) -> Option<BasicBlock> {
+    let bogus_source_info = bogus_source_info(body);
+
+    let bb = body.basic_blocks_mut().get_mut(location.block)?;
+    if location.statement_index >= bb.statements.len() {
+        return None;
+    }
+    let mut tail_statements = bb
+        .statements
+        .drain(location.statement_index + 1..)
+        .collect();
+    let bb_terminator = bb.terminator.take();
+
+    let tail_bb_index = fresh_basic_block(body);
+    let tail_bb = body.basic_blocks_mut().get_mut(tail_bb_index).unwrap();
+    tail_bb.statements.append(&mut tail_statements);
+    tail_bb.terminator = bb_terminator;
+
+    let mutant_bb_index = fresh_basic_block(body);
+    let mutant_bb = body.basic_blocks_mut().get_mut(mutant_bb_index).unwrap();
+    mutant_bb.statements = statements;
+    mutant_bb.terminator = Some(Terminator {
+        source_info: bogus_source_info,
+        kind: terminator,
+    });
+
+    let bb = body.basic_blocks_mut().get_mut(location.block).unwrap();
+    bb.terminator = Some(Terminator {
+        source_info: bogus_source_info,
+        kind: TerminatorKind::FalseEdge {
+            real_target: tail_bb_index,
+            imaginary_target: mutant_bb_index,
+        },
+    });
+
+    Some(mutant_bb_index)
+}
+
 pub(crate) fn bogus_source_info<'tcx>(body: &Body<'tcx>) -> SourceInfo {
     body.local_decls.iter().next().unwrap().source_info
 }
@@ -119,6 +251,154 @@ pub(crate) fn borrowed_places<'graph, 'tcx>(
         })
 }
 
+// Returns every place blocked by a borrow edge satisfying `p`, paired with each
+// current place that blocks it through that edge
+pub(crate) fn borrowed_places_with_blockers<'tcx>(
+    graph: &BorrowsGraph<'tcx>,
+    ctx: CompilerCtxt<'_, 'tcx>,
+    p: fn(BorrowKind) -> bool,
+) -> Vec<(Place<'tcx>, Place<'tcx>)> {
+    let mut pairs = vec![];
+    for edge_ref in graph.edges() {
+        if let BorrowPcgEdgeKind::Borrow(borrow_edge @ BorrowEdge::Local(local_borrow)) =
+            edge_ref.kind()
+            && borrow_edge.kind().iter().any(|kind| p(*kind))
+            && let MaybeOldPlace::Current { place } = local_borrow.blocked_place
+        {
+            pairs.extend(
+                edge_ref
+                    .blocked_by_nodes(ctx)
+                    .flat_map(local_node_to_current_place)
+                    .map(|blocking_place| (place, blocking_place)),
+            );
+        }
+    }
+    pairs
+}
+
+// Returns the place `*arg` for every argument `arg` whose pointee appears as a
+// remote place in `graph`
+pub(crate) fn remote_places<'tcx>(
+    graph: &BorrowsGraph<'tcx>,
+    ctx: CompilerCtxt<'_, 'tcx>,
+) -> HashSet<Place<'tcx>> {
+    graph
+        .edges()
+        .flat_map(|edge_ref| edge_ref.blocked_nodes(ctx).collect::<Vec<_>>())
+        .filter_map(|node| match node {
+            PCGNode::Place(remote_place @ MaybeRemotePlace::Remote(_)) => {
+                maybe_remote_place_to_current_place(ctx.tcx(), remote_place)
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+// Returns every current place that blocks some node in `graph`
+pub(crate) fn blocking_places<'tcx>(
+    graph: &BorrowsGraph<'tcx>,
+    ctx: CompilerCtxt<'_, 'tcx>,
+) -> HashSet<Place<'tcx>> {
+    graph
+        .edges()
+        .flat_map(|edge_ref| {
+            edge_ref
+                .blocked_by_nodes(ctx)
+                .flat_map(local_node_to_current_place)
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+// Returns every place with a capability at `curr` along with every place that
+// blocks some node in the borrows graph at `next`. The latter include places
+// reached through a deref, e.g. `(*r).f`.
+pub(crate) fn places_in_pcg<'tcx>(
+    ctx: CompilerCtxt<'_, 'tcx>,
+    curr: &PcgLocation<'tcx>,
+    next: &PcgLocation<'tcx>,
+    phases: PhasePair,
+) -> HashSet<Place<'tcx>> {
+    let mut places = {
+        let borrows_graph = next.states[phases.next]
+            .borrow_pcg()
+            .graph();
+        blocking_places(borrows_graph, ctx)
+    };
+    places.extend(
+        curr.states[phases.curr]
+            .capabilities()
+            .iter()
+            .map(|(place, _)| place),
+    );
+    places
+}
+
+// Returns true if `place` is reached by dereferencing a shared reference
+pub(crate) fn is_behind_shared_ref<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+    place: MirPlace<'tcx>,
+) -> bool {
+    place.iter_projections().any(|(base, elem)| {
+        elem == ProjectionElem::Deref
+            && base.ty(&body.local_decls, tcx).ty.ref_mutability() == Some(Mutability::Not)
+    })
+}
+
+// Returns true if `place` may be assigned to: it is reached from a local declared
+// `mut` without dereferencing any reference, or through a dereference of a mutable
+// reference or pointer which is not followed by a dereference of a shared one
+pub(crate) fn is_assignable<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+    place: MirPlace<'tcx>,
+) -> bool {
+    let mut mutability = body.local_decls[place.local].mutability;
+    for (base, elem) in place.iter_projections() {
+        if elem != ProjectionElem::Deref {
+            continue;
+        }
+        // Dereferencing a `Box` keeps the mutability of the box itself
+        match base.ty(&body.local_decls, tcx).ty.kind() {
+            TyKind::Ref(_, _, ref_mutability) | TyKind::RawPtr(_, ref_mutability) => {
+                mutability = *ref_mutability;
+            }
+            _ => {}
+        }
+    }
+    mutability == Mutability::Mut
+}
+
+// Returns true if `place` is reached by dereferencing any reference
+pub(crate) fn is_behind_ref<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+    place: MirPlace<'tcx>,
+) -> bool {
+    place.iter_projections().any(|(base, elem)| {
+        elem == ProjectionElem::Deref && base.ty(&body.local_decls, tcx).ty.is_ref()
+    })
+}
+
+// Returns the current place corresponding to every labelled old place that is
+// blocked by some edge in `graph`
+pub(crate) fn old_blocked_places<'tcx>(
+    graph: &BorrowsGraph<'tcx>,
+    ctx: CompilerCtxt<'_, 'tcx>,
+) -> HashSet<Place<'tcx>> {
+    graph
+        .edges()
+        .flat_map(|edge_ref| edge_ref.blocked_nodes(ctx).collect::<Vec<_>>())
+        .filter_map(|node| match node {
+            PCGNode::Place(MaybeRemotePlace::Local(maybe_old_place)) => {
+                old_place_to_current_place(maybe_old_place)
+            }
+            _ => None,
+        })
+        .collect()
+}
+
 pub(crate) fn has_named_local<'tcx>(
     place: Place<'tcx>,
     body: &Body<'tcx>,
@@ -130,3 +410,58 @@ pub(crate) fn has_named_local<'tcx>(
         None => false,
     }
 }
+
+// Returns true if values of type `ty` can be copied out of a place
+pub(crate) fn is_copy<'tcx>(tcx: TyCtxt<'tcx>, body: &Body<'tcx>, ty: Ty<'tcx>) -> bool {
+    tcx.type_is_copy_modulo_regions(body.typing_env(tcx), ty)
+}
+
+// Returns true if one of `place` and `other` is a prefix of the other
+pub(crate) fn places_overlap<'tcx>(place: Place<'tcx>, other: Place<'tcx>) -> bool {
+    place.local == other.local
+        && place
+            .projection
+            .iter()
+            .zip(other.projection.iter())
+            .all(|(elem, other_elem)| elem == other_elem)
+}
+
+// Caches a value computed from the body that a `Mutation` is run on, so that it is
+// computed once per body rather than once per mutation site. A `Mutation` outlives
+// the bodies it is run on, so the value cannot borrow from them.
+pub(crate) struct BodyCache<T> {
+    cached: RefCell<Option<(DefId, T)>>,
+}
+
+impl<T> Default for BodyCache<T> {
+    fn default() -> Self {
+        Self {
+            cached: RefCell::new(None),
+        }
+    }
+}
+
+impl<T> BodyCache<T> {
+    // Calls `f` on the value cached for `body`, computing it first if `body` is not
+    // the body the cached value was computed from
+    pub(crate) fn with_mut<R>(
+        &self,
+        body: &Body<'_>,
+        compute: impl FnOnce() -> T,
+        f: impl FnOnce(&mut T) -> R,
+    ) -> R {
+        let def_id = body.source.def_id();
+        let mut cached = self.cached.borrow_mut();
+        if !matches!(&*cached, Some((cached_def_id, _)) if *cached_def_id == def_id) {
+            *cached = Some((def_id, compute()));
+        }
+        let (_, value) = cached.as_mut().unwrap();
+        f(value)
+    }
+}
+
+impl<T: Clone> BodyCache<T> {
+    pub(crate) fn get_or_compute(&self, body: &Body<'_>, compute: impl FnOnce() -> T) -> T {
+        self.with_mut(body, compute, |value| value.clone())
+    }
+}
@@ -0,0 +1,234 @@
+use crate::rustc_interface::const_eval::util::relate_types;
+
+use crate::rustc_interface::middle::mir::tcx::PlaceTy;
+use crate::rustc_interface::middle::mir::visit::PlaceContext;
+use crate::rustc_interface::middle::mir::visit::Visitor;
+use crate::rustc_interface::middle::mir::Body;
+use crate::rustc_interface::middle::mir::Const;
+use crate::rustc_interface::middle::mir::ConstOperand;
+use crate::rustc_interface::middle::mir::ConstValue;
+use crate::rustc_interface::middle::mir::Local;
+use crate::rustc_interface::middle::mir::Location;
+use crate::rustc_interface::middle::mir::Place as MirPlace;
+use crate::rustc_interface::middle::mir::ProjectionElem;
+use crate::rustc_interface::middle::mir::Rvalue;
+use crate::rustc_interface::middle::mir::Statement;
+use crate::rustc_interface::middle::mir::StatementKind;
+use crate::rustc_interface::middle::mir::UnwindAction;
+use crate::rustc_interface::middle::ty::TyCtxt;
+use crate::rustc_interface::middle::ty::TyKind;
+use crate::rustc_interface::middle::ty::TypeVisitableExt;
+use crate::rustc_interface::middle::ty::Variance;
+
+// Checks that a mutant body is well-formed MIR, so that the borrow checker only
+// sees mutants that a correct MIR transformation could have produced. The
+// validator in `rustc_mir_transform` is private and reports failures as ICEs, so
+// this is a hand-written partial copy of the parts of its CFG and type checks that
+// mutants can violate. It does not check:
+// - that field projections name an existing field, nor the field types
+// - the types of call arguments and destinations, or of other terminator operands
+// - that the mutability of a `Ref` rvalue is allowed for the borrowed place
+// Checking a body that is malformed in one of these ways may panic, which is
+// reported as the body being invalid. Returns the reason the body is invalid, if any.
+pub fn validate_mutant<'tcx>(tcx: TyCtxt<'tcx>, body: &Body<'tcx>) -> Result<(), String> {
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| validate(tcx, body))).unwrap_or_else(
+        |payload| {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            Err(format!("validation panicked: {}", message))
+        },
+    )
+}
+
+fn validate<'tcx>(tcx: TyCtxt<'tcx>, body: &Body<'tcx>) -> Result<(), String> {
+    validate_cfg(body)?;
+
+    // Computing the type of a place or rvalue panics on undeclared locals, so
+    // check the locals before the types
+    let mut local_checker = LocalChecker { body, error: None };
+    local_checker.visit_body(body);
+    if let Some(reason) = local_checker.error {
+        return Err(reason);
+    }
+
+    let mut type_checker = TypeChecker {
+        tcx,
+        body,
+        error: None,
+    };
+    type_checker.visit_body(body);
+    match type_checker.error {
+        Some(reason) => Err(reason),
+        None => Ok(()),
+    }
+}
+
+// Every block must end in a terminator whose successors exist. Cleanup blocks may
+// only be entered by unwinding, and only from blocks that are not cleanup blocks.
+fn validate_cfg<'tcx>(body: &Body<'tcx>) -> Result<(), String> {
+    for (bb, data) in body.basic_blocks.iter_enumerated() {
+        let Some(terminator) = &data.terminator else {
+            return Err(format!("{:?} has no terminator", bb));
+        };
+        let unwind_target = match terminator.unwind() {
+            Some(UnwindAction::Cleanup(target)) => Some(*target),
+            _ => None,
+        };
+        for target in terminator.successors() {
+            let Some(target_data) = body.basic_blocks.get(target) else {
+                return Err(format!(
+                    "{:?} jumps to {:?}, which does not exist",
+                    bb, target
+                ));
+            };
+            if Some(target) == unwind_target {
+                if data.is_cleanup || !target_data.is_cleanup {
+                    return Err(format!(
+                        "{:?} unwinds to {:?}, but only non-cleanup blocks may unwind into cleanup blocks",
+                        bb, target
+                    ));
+                }
+            } else if data.is_cleanup != target_data.is_cleanup {
+                return Err(format!(
+                    "{:?} jumps to {:?} across a cleanup boundary",
+                    bb, target
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+struct LocalChecker<'a, 'tcx> {
+    body: &'a Body<'tcx>,
+    error: Option<String>,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for LocalChecker<'a, 'tcx> {
+    fn visit_local(&mut self, local: Local, _context: PlaceContext, location: Location) {
+        if self.error.is_none() && self.body.local_decls.get(local).is_none() {
+            self.error = Some(format!("{:?} at {:?} is not declared", local, location));
+        }
+    }
+}
+
+struct TypeChecker<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    body: &'a Body<'tcx>,
+    error: Option<String>,
+}
+
+impl<'a, 'tcx> TypeChecker<'a, 'tcx> {
+    fn fail(&mut self, location: Location, reason: String) {
+        if self.error.is_none() {
+            self.error = Some(format!("{} at {:?}", reason, location));
+        }
+    }
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for TypeChecker<'a, 'tcx> {
+    // Each projection must apply to the type of the place it projects from
+    fn visit_place(&mut self, place: &MirPlace<'tcx>, _context: PlaceContext, location: Location) {
+        let mut place_ty = PlaceTy::from_ty(self.body.local_decls[place.local].ty);
+        for elem in place.projection.iter() {
+            let valid = match elem {
+                ProjectionElem::Deref => place_ty.ty.builtin_deref(true).is_some(),
+                ProjectionElem::Index(index) => {
+                    place_ty.ty.builtin_index().is_some()
+                        && self.body.local_decls[index].ty == self.tcx.types.usize
+                }
+                ProjectionElem::ConstantIndex { .. } | ProjectionElem::Subslice { .. } => {
+                    place_ty.ty.builtin_index().is_some()
+                }
+                ProjectionElem::Field(..) => matches!(
+                    place_ty.ty.kind(),
+                    TyKind::Adt(..)
+                        | TyKind::Tuple(..)
+                        | TyKind::Closure(..)
+                        | TyKind::CoroutineClosure(..)
+                        | TyKind::Coroutine(..)
+                ),
+                ProjectionElem::Downcast(..) => place_ty.ty.is_enum() || place_ty.ty.is_coroutine(),
+                ProjectionElem::OpaqueCast(..) | ProjectionElem::Subtype(..) => true,
+            };
+            if !valid {
+                self.fail(
+                    location,
+                    format!(
+                        "{:?} cannot be projected with {:?} in {:?}",
+                        place_ty.ty, elem, place
+                    ),
+                );
+                return;
+            }
+            place_ty = place_ty.projection_ty(self.tcx, elem);
+        }
+    }
+
+    // A zero-sized constant must have a zero-sized type. Types whose layout is not
+    // known, e.g. because they are generic, are not checked.
+    fn visit_const_operand(&mut self, constant: &ConstOperand<'tcx>, location: Location) {
+        let Const::Val(ConstValue::ZeroSized, ty) = constant.const_ else {
+            return;
+        };
+        let typing_env = self.body.typing_env(self.tcx);
+        if self
+            .tcx
+            .layout_of(typing_env.as_query_input(self.tcx.erase_regions(ty)))
+            .is_ok_and(|layout| !layout.is_zst())
+        {
+            self.fail(location, format!("zero-sized constant has non-zero-sized type {:?}", ty));
+        }
+    }
+
+    // The value assigned by a statement must be a subtype of the place it is assigned
+    // to, and must be sized
+    fn visit_statement(&mut self, statement: &Statement<'tcx>, location: Location) {
+        self.super_statement(statement, location);
+        if self.error.is_some() {
+            return;
+        }
+        let StatementKind::Assign(assign) = &statement.kind else {
+            return;
+        };
+        let (place, rvalue) = &**assign;
+        if let Rvalue::Len(len_place) = rvalue {
+            let len_place_ty = len_place.ty(self.body, self.tcx).ty;
+            if len_place_ty.builtin_index().is_none() {
+                self.fail(location, format!("{:?} has no length", len_place_ty));
+                return;
+            }
+        }
+        // Region variables in mutants are not related by any inference context, so
+        // only compare the types modulo regions, like the rustc validator does
+        let typing_env = self.body.typing_env(self.tcx);
+        let place_ty = self.tcx.erase_regions(place.ty(self.body, self.tcx).ty);
+        let rvalue_ty = self.tcx.erase_regions(rvalue.ty(self.body, self.tcx));
+        if !place_ty.is_sized(self.tcx, typing_env) {
+            self.fail(location, format!("unsized {:?} of type {:?} is assigned", place, place_ty));
+            return;
+        }
+        // Like the rustc validator, we do not relate opaque types
+        if (place_ty, rvalue_ty).has_opaque_types() {
+            return;
+        }
+        if !relate_types(
+            self.tcx,
+            typing_env,
+            Variance::Covariant,
+            rvalue_ty,
+            place_ty,
+        ) {
+            self.fail(
+                location,
+                format!(
+                    "{:?} of type {:?} is assigned to {:?} of type {:?}",
+                    rvalue, rvalue_ty, place, place_ty
+                ),
+            );
+        }
+    }
+}
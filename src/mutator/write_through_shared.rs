@@ -0,0 +1,112 @@
+use super::utils::bogus_operand;
+use super::utils::has_named_local;
+use super::utils::is_behind_shared_ref;
+use super::utils::places_in_pcg;
+
+use std::collections::HashSet;
+
+use super::mutator_impl::Mutant;
+use super::mutator_impl::MutantBuilder;
+use super::mutator_impl::MutantIterator;
+use super::mutator_impl::MutantStream;
+use super::mutator_impl::Mutation;
+use super::mutator_impl::PhasePair;
+
+use crate::rustc_interface::middle::mir::Body;
+use crate::rustc_interface::middle::mir::Mutability;
+use crate::rustc_interface::middle::mir::Place as MirPlace;
+use crate::rustc_interface::middle::mir::PlaceRef;
+use crate::rustc_interface::middle::mir::Rvalue;
+use crate::rustc_interface::middle::mir::StatementKind;
+
+use pcg::free_pcs::PcgLocation;
+use pcg::utils::CompilerCtxt;
+
+// `WriteThroughShared` creates mutants which write to places reached through a
+// shared reference
+pub struct WriteThroughShared;
+
+struct Iter<'a, 'tcx: 'a> {
+    behind_shared: Vec<MirPlace<'tcx>>,
+    ctx: CompilerCtxt<'a, 'tcx>,
+    body: &'a Body<'tcx>,
+    curr: PcgLocation<'tcx>,
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> MutantIterator<'a, 'mir, 'tcx> for Iter<'a, 'tcx> {
+    fn next(&mut self) -> Option<Mutant<'tcx>> {
+        while !self.behind_shared.is_empty() {
+            if let Some(mutant) = self.maybe_next() {
+                return Some(mutant);
+            }
+        }
+        return None;
+    }
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> Iter<'a, 'tcx> {
+    fn maybe_next(&mut self) -> Option<Mutant<'tcx>> {
+        let shared_place = self.behind_shared.pop()?;
+        let mut builder = MutantBuilder::new(self.ctx.tcx(), self.body);
+
+        let new_value = bogus_operand(self.ctx.tcx(), self.body, shared_place)?;
+
+        // Write to `shared_place` between `curr` and `next`
+        builder.insert_after(
+            self.curr.location,
+            StatementKind::Assign(Box::new((shared_place, Rvalue::Use(new_value)))),
+        )?;
+
+        builder.build(format!("{:?} is behind a shared reference", shared_place))
+    }
+}
+
+impl Mutation for WriteThroughShared {
+    fn make_stream<'a, 'mir: 'a, 'tcx: 'mir>(
+        &self,
+        ctx: CompilerCtxt<'a, 'tcx>,
+        body: &'a Body<'tcx>,
+        curr: PcgLocation<'tcx>,
+        next: PcgLocation<'tcx>,
+        phases: PhasePair,
+    ) -> MutantStream<'a, 'mir, 'tcx> {
+        let tcx = ctx.tcx();
+
+        let places = places_in_pcg(ctx, &curr, &next, phases);
+
+        let behind_shared = places
+            .into_iter()
+            .filter(|place| has_named_local(*place, body))
+            .map(|place| PlaceRef::from(*place).to_place(tcx))
+            .map(|place| {
+                // A shared reference itself is not behind a shared reference,
+                // but what it points to is
+                if place.ty(&body.local_decls, tcx).ty.ref_mutability() == Some(Mutability::Not) {
+                    tcx.mk_place_deref(place)
+                } else {
+                    place
+                }
+            })
+            .filter(|place| is_behind_shared_ref(tcx, body, *place))
+            // Unsized values, e.g. behind a `&str`, cannot be assigned
+            .filter(|place| {
+                place
+                    .ty(&body.local_decls, tcx)
+                    .ty
+                    .is_sized(tcx, body.typing_env(tcx))
+            })
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+
+        MutantStream::new(Box::new(Iter {
+            behind_shared,
+            ctx,
+            body,
+            curr,
+        }))
+    }
+    fn name(&self) -> String {
+        "write-through-shared".into()
+    }
+}
@@ -0,0 +1,129 @@
+use super::utils::bogus_operand;
+use super::utils::borrowed_places;
+use super::utils::has_named_local;
+use super::utils::old_blocked_places;
+use super::utils::places_overlap;
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use super::mutator_impl::Mutant;
+use super::mutator_impl::MutantBuilder;
+use super::mutator_impl::MutantIterator;
+use super::mutator_impl::MutantStream;
+use super::mutator_impl::Mutation;
+use super::mutator_impl::PhasePair;
+
+use crate::ExpectedOutcome;
+
+use crate::rustc_interface::middle::mir::Body;
+use crate::rustc_interface::middle::mir::PlaceRef;
+use crate::rustc_interface::middle::mir::Rvalue;
+use crate::rustc_interface::middle::mir::StatementKind;
+
+use pcg::free_pcs::CapabilityKind;
+use pcg::free_pcs::PcgLocation;
+use pcg::utils::CompilerCtxt;
+use pcg::utils::Place;
+
+// `WriteToRelabelled` creates mutants which write to a place `x` while the old
+// place `old(x)` is still blocked in the borrows graph. The PCG labels `x` as old
+// once it no longer refers to the borrowed memory, so the borrow checker should
+// accept all of them.
+pub struct WriteToRelabelled;
+
+struct Iter<'a, 'tcx: 'a> {
+    relabelled: Vec<Place<'tcx>>,
+    ctx: CompilerCtxt<'a, 'tcx>,
+    body: &'a Body<'tcx>,
+    curr: PcgLocation<'tcx>,
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> MutantIterator<'a, 'mir, 'tcx> for Iter<'a, 'tcx> {
+    fn next(&mut self) -> Option<Mutant<'tcx>> {
+        while !self.relabelled.is_empty() {
+            if let Some(mutant) = self.maybe_next() {
+                return Some(mutant);
+            }
+        }
+        return None;
+    }
+}
+
+impl<'a, 'mir: 'a, 'tcx: 'mir> Iter<'a, 'tcx> {
+    fn maybe_next(&mut self) -> Option<Mutant<'tcx>> {
+        let place = self.relabelled.pop()?;
+        let current_place = PlaceRef::from(*place).to_place(self.ctx.tcx());
+        let mut builder = MutantBuilder::new(self.ctx.tcx(), self.body);
+
+        let new_value = bogus_operand(self.ctx.tcx(), self.body, current_place)?;
+
+        // Overwrite `current_place` between `curr` and `next`
+        builder.insert_after(
+            self.curr.location,
+            StatementKind::Assign(Box::new((current_place, Rvalue::Use(new_value)))),
+        )?;
+
+        builder.build(format!("old({:?}) was blocked", current_place))
+    }
+}
+
+impl Mutation for WriteToRelabelled {
+    fn make_stream<'a, 'mir: 'a, 'tcx: 'mir>(
+        &self,
+        ctx: CompilerCtxt<'a, 'tcx>,
+        body: &'a Body<'tcx>,
+        curr: PcgLocation<'tcx>,
+        next: PcgLocation<'tcx>,
+        phases: PhasePair,
+    ) -> MutantStream<'a, 'mir, 'tcx> {
+        let (old_in_next, lent_in_next) = {
+            let borrows_graph = next.states[phases.next]
+                .borrow_pcg()
+                .graph();
+            (
+                old_blocked_places(borrows_graph, ctx),
+                borrowed_places(borrows_graph, |_| true)
+                    .map(|(place, _)| place)
+                    .collect::<HashSet<_>>(),
+            )
+        };
+
+        // We only write to owned places that are initialized and can be written to at both
+        // `curr` and `next`. A place with only `Write` capability may be a field of an
+        // uninitialized place, which the borrow checker rejects writing to.
+        let writable_in_next: HashMap<_, _> = next.states[phases.next]
+            .capabilities()
+            .iter()
+            .filter(|(_, ck)| matches!(ck, CapabilityKind::Exclusive))
+            .collect();
+
+        let relabelled = curr.states[phases.curr]
+            .capabilities()
+            .iter()
+            .filter(|(_, ck)| matches!(ck, CapabilityKind::Exclusive))
+            .map(|(place, _)| place)
+            .filter(|place| writable_in_next.contains_key(place))
+            .filter(|place| old_in_next.contains(place))
+            .filter(|place| {
+                !lent_in_next
+                    .iter()
+                    .any(|lent_place| places_overlap(*place, *lent_place))
+            })
+            .filter(|place| has_named_local(*place, body))
+            .collect();
+
+        MutantStream::new(Box::new(Iter {
+            relabelled,
+            ctx,
+            body,
+            curr,
+        }))
+    }
+    fn name(&self) -> String {
+        "write-to-relabelled".into()
+    }
+    fn expected_outcome(&self) -> ExpectedOutcome {
+        ExpectedOutcome::Pass
+    }
+}
@@ -1,4 +1,3 @@
-use super::utils::bogus_source_info;
 use super::utils::borrowed_places;
 use super::utils::has_named_local;
 use super::utils::is_shared;
@@ -6,20 +5,18 @@ use super::utils::is_shared;
 use std::collections::HashSet;
 
 use super::mutator_impl::Mutant;
+use super::mutator_impl::MutantBuilder;
 use super::mutator_impl::MutantIterator;
-use super::mutator_impl::MutantLocation;
-use super::mutator_impl::MutantRange;
 use super::mutator_impl::MutantStream;
 use super::mutator_impl::Mutation;
+use super::mutator_impl::PhasePair;
 
 use crate::rustc_interface::middle::mir::Body;
 use crate::rustc_interface::middle::mir::PlaceRef;
 use crate::rustc_interface::middle::mir::Rvalue;
-use crate::rustc_interface::middle::mir::Statement;
 use crate::rustc_interface::middle::mir::StatementKind;
 
 use pcg::free_pcs::PcgLocation;
-use pcg::pcg::EvalStmtPhase;
 use pcg::utils::CompilerCtxt;
 use pcg::utils::Place;
 
@@ -37,43 +34,15 @@ impl<'a, 'mir: 'a, 'tcx: 'mir> Iter<'a, 'tcx> {
     fn maybe_next(&mut self) -> Option<Mutant<'tcx>> {
         let place = self.shared.pop()?;
         let shared_place = PlaceRef::from(*place).to_place(self.ctx.tcx());
-        let mut mutant_body = self.body.clone();
+        let mut builder = MutantBuilder::new(self.ctx.tcx(), self.body);
 
-        let statement_index = self.curr.location.statement_index;
+        // Write to `shared_place` between `curr` and `next`
+        builder.insert_after(
+            self.curr.location,
+            StatementKind::Assign(Box::new((shared_place, Rvalue::Len(shared_place)))),
+        )?;
 
-        // Statment which writes to `shared_place`
-        let new_assign = Statement {
-            source_info: bogus_source_info(&mutant_body),
-            kind: StatementKind::Assign(Box::new((shared_place, Rvalue::Len(shared_place)))),
-        };
-        let info = format!(
-            "{:?} was shared, so inserted {:?}",
-            shared_place, &new_assign
-        );
-
-        let bb_index = self.curr.location.block;
-        let bb = mutant_body.basic_blocks_mut().get_mut(bb_index)?;
-        // Insert `new_assign` between `curr` and `next`
-        bb.statements.insert(statement_index + 1, new_assign);
-
-        let borrow_loc = MutantLocation {
-            basic_block: self.curr.location.block.index(),
-            statement_index: statement_index + 1,
-        };
-
-        let mention_loc = MutantLocation {
-            basic_block: self.curr.location.block.index(),
-            statement_index: statement_index + 1,
-        };
-
-        Some(Mutant {
-            body: mutant_body,
-            range: MutantRange {
-                start: borrow_loc,
-                end: mention_loc,
-            },
-            info,
-        })
+        builder.build(format!("{:?} was shared", shared_place))
     }
 }
 
@@ -95,16 +64,17 @@ impl Mutation for WriteToShared {
         body: &'a Body<'tcx>,
         curr: PcgLocation<'tcx>,
         next: PcgLocation<'tcx>,
+        phases: PhasePair,
     ) -> MutantStream<'a, 'mir, 'tcx> {
         let shared_in_curr = {
-            let borrows_graph = curr.states[EvalStmtPhase::PostMain].borrow_pcg().graph();
+            let borrows_graph = curr.states[phases.curr].borrow_pcg().graph();
             borrowed_places(borrows_graph, is_shared)
                 .map(|(place, _)| place)
                 .collect::<HashSet<_>>()
         };
 
         let shared_in_next = {
-            let borrows_graph = next.states[EvalStmtPhase::PostOperands]
+            let borrows_graph = next.states[phases.next]
                 .borrow_pcg()
                 .graph();
             borrowed_places(borrows_graph, is_shared)
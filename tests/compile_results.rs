@@ -40,16 +40,21 @@ fn compile_results() -> io::Result<()> {
                         let entry = mutator_results
                           .entry(mutator_name)
                           .or_insert(MutatorData {
+                              expected: result.expected,
                               instances: 0,
                               passed: 0,
                               failed: 0,
                               panicked: 0,
+                              invalid: 0,
+                              unexpected: 0,
                               error_codes: HashSet::new(),
                           });
                         entry.instances += result.instances;
                         entry.passed += result.passed;
                         entry.failed += result.failed;
                         entry.panicked += result.panicked;
+                        entry.invalid += result.invalid;
+                        entry.unexpected += result.unexpected;
                         entry.error_codes.extend(result.error_codes);
                     }
                 }
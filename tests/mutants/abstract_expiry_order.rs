@@ -0,0 +1,13 @@
+// `q` is derived from a mutable borrow of `x` through a call, so `x` cannot be
+// used before `q` expires
+fn first<'a>(r: &'a mut i32) -> &'a mut i32 {
+    r
+}
+
+pub fn reborrow_through_call() {
+    let mut x = 0;
+    let q = first(&mut x);
+    let n = 1;
+    let _ = n;
+    *q += 1;
+}
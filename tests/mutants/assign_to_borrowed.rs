@@ -0,0 +1,11 @@
+// `x` is mutably borrowed by `r` until `r` is passed to `use_ref`, so `x` cannot
+// be assigned in between
+fn use_ref(_r: &mut String) {}
+
+pub fn assign_while_borrowed() {
+    let mut x = String::new();
+    let r = &mut x;
+    let n = 1;
+    use_ref(r);
+    let _ = n;
+}
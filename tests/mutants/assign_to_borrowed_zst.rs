@@ -0,0 +1,13 @@
+// `u` is zero-sized and `t` has no known layout. Overwriting `u` while it is
+// borrowed must still produce MIR that passes validation, and `t` is skipped.
+fn use_ref<U>(_r: &U) {}
+
+pub fn assign_to_borrowed_zst<T>(t: T) {
+    let u = ();
+    let r = &u;
+    let s = &t;
+    let n = 1;
+    let _ = n;
+    use_ref(r);
+    use_ref(s);
+}
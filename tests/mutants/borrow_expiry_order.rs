@@ -0,0 +1,12 @@
+// `s` reborrows `*r`, so `r` cannot be used before `s` expires
+fn use_ref(_r: &mut i32) {}
+
+pub fn nested_reborrows() {
+    let mut x = 0;
+    let r = &mut x;
+    let s = &mut *r;
+    let n = 1;
+    let _ = n;
+    use_ref(s);
+    use_ref(r);
+}
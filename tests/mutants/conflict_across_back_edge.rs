@@ -0,0 +1,12 @@
+// `r` borrows `a` across every iteration of the loop and is used after it, so
+// `a` cannot be mutably borrowed before the back edge
+pub fn increment_in_loop(mut a: i32, n: usize) -> i32 {
+    let r = &mut a;
+    let mut i = 0;
+    while i < n {
+        *r += 1;
+        i += 1;
+    }
+    *r += 1;
+    a
+}
@@ -0,0 +1,17 @@
+// `c` captures `x` by mutable reference until it is called, so `x` cannot be
+// read in between; `s` is captured by shared reference, so it cannot be
+// mutably borrowed in between
+pub fn use_captured_places() {
+    let mut x = 0;
+    let mut c = || x += 1;
+    let n = 1;
+    let _ = n;
+    c();
+
+    let mut s = String::new();
+    let d = || s.len();
+    let m = 1;
+    let _ = m;
+    d();
+    s.push('x');
+}
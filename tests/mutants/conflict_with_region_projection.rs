@@ -0,0 +1,15 @@
+// `q` is derived from the lifetime of the mutable reference held by `p`, so `p`
+// cannot be mutably borrowed while `q` is live
+fn first<'a>(r: &'a mut i32) -> &'a mut i32 {
+    r
+}
+
+pub fn reborrow_through_call() {
+    let mut x = 0;
+    let mut p = &mut x;
+    let q = first(p);
+    let n = 1;
+    let _ = n;
+    *q += 1;
+    *p += 1;
+}
@@ -0,0 +1,11 @@
+// `x` is borrowed by `r` until `r` is passed to `use_ref`, so `x` cannot be
+// dropped in between
+fn use_ref(_r: &String) {}
+
+pub fn drop_while_borrowed() {
+    let x = String::new();
+    let r = &x;
+    let n = 1;
+    use_ref(r);
+    let _ = n;
+}
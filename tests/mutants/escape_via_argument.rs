@@ -0,0 +1,7 @@
+// A reference to the local `x` cannot be stored behind the argument `out`
+pub fn escape_via_argument(out: &mut &i32) {
+    let x = 0;
+    let _ = x;
+    let n = 1;
+    let _ = n;
+}
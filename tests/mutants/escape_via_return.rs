@@ -0,0 +1,6 @@
+// A reference to the local `x` cannot be returned
+pub fn escape_via_return(a: &i32) -> &i32 {
+    let x = 0;
+    let _ = x;
+    a
+}
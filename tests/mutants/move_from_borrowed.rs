@@ -0,0 +1,12 @@
+// `s` is mutably borrowed by `r` until `r` is passed to `use_ref`, so `s` cannot
+// be moved out of in between
+fn use_ref(_r: &mut String) {}
+
+pub fn move_while_mutably_borrowed() {
+    let mut s = String::new();
+    let r = &mut s;
+    let n = 1;
+    let _ = n;
+    use_ref(r);
+    drop(s);
+}
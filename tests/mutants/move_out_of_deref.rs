@@ -0,0 +1,8 @@
+// `*s` is behind a shared reference, so the `String` cannot be moved out of it
+fn use_ref(_s: &String) {}
+
+pub fn move_out_of_shared_ref(s: &String) {
+    let n = 1;
+    let _ = n;
+    use_ref(s);
+}
@@ -0,0 +1,6 @@
+// Elements of `xs` are not `Copy`, so none of them can be moved out by indexing
+pub fn move_out_of_array(xs: [String; 2]) {
+    let n = 1;
+    let _ = n;
+    drop(xs);
+}
@@ -0,0 +1,17 @@
+// `p.a` is mutably borrowed by `r`, which leaves its sibling `p.b` free to be
+// borrowed mutably as well
+pub struct Pair {
+    a: i32,
+    b: i32,
+}
+
+fn use_ref(_r: &mut i32) {}
+
+pub fn borrow_disjoint_fields() {
+    let mut p = Pair { a: 0, b: 1 };
+    let r = &mut p.a;
+    let n = 1;
+    let _ = n;
+    use_ref(r);
+    let _ = p.b;
+}
@@ -0,0 +1,11 @@
+// `x` is shared-borrowed by `r` until `r` is passed to `use_ref`, so `x` cannot
+// be mutably borrowed in between
+fn use_ref(_r: &i32) {}
+
+pub fn mutably_borrow_while_shared_borrowed() {
+    let mut x = 0;
+    let r = &x;
+    let n = 1;
+    let _ = n;
+    use_ref(r);
+}
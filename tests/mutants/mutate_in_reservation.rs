@@ -0,0 +1,5 @@
+// `v.push` reserves a two-phase mutable borrow of `v` before `v.len()` is
+// evaluated, so `v` may be read but not mutated until the borrow is activated
+pub fn push_len(mut v: Vec<usize>) {
+    v.push(v.len());
+}
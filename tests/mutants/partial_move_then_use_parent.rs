@@ -0,0 +1,16 @@
+// Moving `p.c` expands `p`; once `p.a` is also moved out, `p` cannot be read as
+// a whole
+pub struct Triple {
+    a: String,
+    b: String,
+    c: String,
+}
+
+pub fn use_after_partial_move(p: Triple) {
+    let c = p.c;
+    let n = 1;
+    let _ = n;
+    drop(p.a);
+    drop(p.b);
+    drop(c);
+}
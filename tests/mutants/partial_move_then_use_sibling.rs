@@ -0,0 +1,15 @@
+// Moving `p.c` expands `p`; moving `p.a` out afterwards leaves `p.b` readable
+pub struct Triple {
+    a: String,
+    b: String,
+    c: String,
+}
+
+pub fn use_sibling_after_partial_move(p: Triple) {
+    let c = p.c;
+    let n = 1;
+    let _ = n;
+    drop(p.a);
+    drop(p.b);
+    drop(c);
+}
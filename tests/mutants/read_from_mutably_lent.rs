@@ -0,0 +1,11 @@
+// `x` is mutably borrowed by `r` until `r` is passed to `use_ref`, so `x` cannot
+// be read in between
+fn use_ref(_r: &mut i32) {}
+
+pub fn read_while_mutably_borrowed() {
+    let mut x = 0;
+    let r = &mut x;
+    let n = 1;
+    use_ref(r);
+    let _ = n;
+}
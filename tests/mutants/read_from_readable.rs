@@ -0,0 +1,11 @@
+// `x` is readable throughout, and `s` is readable while only shared-borrowed by
+// `r`
+fn use_ref(_r: &String) {}
+
+pub fn read_readable_places(s: String) {
+    let x = 0;
+    let r = &s;
+    let n = x + 1;
+    let _ = n;
+    use_ref(r);
+}
@@ -0,0 +1,9 @@
+// `x` is declared before it is initialized, so it cannot be read until it is
+// assigned
+pub fn read_before_init() {
+    let x: String;
+    let n = 1;
+    let _ = n;
+    x = String::new();
+    drop(x);
+}
@@ -0,0 +1,11 @@
+// `x` is borrowed by `r` until `r` is passed to `use_ref`, so the storage of `x`
+// cannot end in between
+fn use_ref(_r: &i32) {}
+
+pub fn storage_dead_while_borrowed() {
+    let x = 0;
+    let r = &x;
+    let n = 1;
+    use_ref(r);
+    let _ = n;
+}
@@ -0,0 +1,9 @@
+// Reading `*r` is the last use of `r`, so `x` may be overwritten right after it
+// but not before it
+pub fn overwrite_after_last_use() -> i32 {
+    let mut x = 0;
+    let r = &x;
+    let y = *r;
+    x = 1;
+    x + y
+}
@@ -0,0 +1,8 @@
+// `s` is moved into `t`, so reading `s` afterwards is a use after move
+fn consume(_s: String) {}
+
+pub fn move_then_consume() {
+    let s = String::new();
+    let t = s;
+    consume(t);
+}
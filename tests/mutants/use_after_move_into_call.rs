@@ -0,0 +1,9 @@
+// `s` is moved into `consume` by the call terminator, so it cannot be read at
+// the start of the block the call returns to
+fn consume(_s: String) {}
+
+pub fn read_after_call(s: String) {
+    consume(s);
+    let n = 1;
+    let _ = n;
+}
@@ -0,0 +1,6 @@
+// The operand of `let t = s;` moves `s`, so `s` cannot be read between the
+// evaluation of the operand and the assignment to `t`
+pub fn read_during_move(s: String) {
+    let t = s;
+    drop(t);
+}
@@ -0,0 +1,7 @@
+// `a` is a mutable reference provided by the caller and its pointee is never
+// reborrowed, so it can be written through anywhere
+pub fn write_mut_argument(a: &mut i32) {
+    let n = 1;
+    let _ = n;
+    *a += 1;
+}
@@ -0,0 +1,10 @@
+// `r` is a shared reference to `x`, so nothing can be written through it
+fn use_ref(_r: &i32) {}
+
+pub fn write_through_shared_ref() {
+    let x = 0;
+    let r = &x;
+    let n = 1;
+    let _ = n;
+    use_ref(r);
+}
@@ -0,0 +1,7 @@
+// `a` is a shared reference provided by the caller, so nothing can be written
+// through it
+pub fn read_shared_argument(a: &i32) -> i32 {
+    let n = 1;
+    let _ = n;
+    *a + 1
+}
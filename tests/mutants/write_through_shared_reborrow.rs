@@ -0,0 +1,13 @@
+// `s` reborrows `*r` as shared until it is passed to `use_ref`, so `*r` cannot
+// be written to in between
+fn use_ref(_r: &i32) {}
+
+pub fn shared_reborrow_of_mut_ref() {
+    let mut x = 0;
+    let r = &mut x;
+    let s = &*r;
+    let n = 1;
+    let _ = n;
+    use_ref(s);
+    *r += 1;
+}
@@ -0,0 +1,10 @@
+// `x` and `s` are owned, declared `mut` and not borrowed, so they can be
+// overwritten
+pub fn write_exclusive_places(mut s: String) {
+    let mut x = 0;
+    let n = 1;
+    let _ = n;
+    x += 1;
+    s.push('x');
+    let _ = x;
+}
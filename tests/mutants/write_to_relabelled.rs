@@ -0,0 +1,15 @@
+// `y` reborrows the old value of `*x`, so after `x` is reassigned, `x` can be
+// overwritten again without affecting `y`
+fn use_ref(_r: &mut i32) {}
+
+pub fn reassign_reborrowed_ref() {
+    let mut a = 0;
+    let mut b = 1;
+    let mut x = &mut a;
+    let y = &mut *x;
+    x = &mut b;
+    let n = 1;
+    let _ = n;
+    use_ref(y);
+    use_ref(x);
+}
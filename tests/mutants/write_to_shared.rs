@@ -0,0 +1,12 @@
+// `x` is shared-borrowed by `r` until `r` is passed to `use_ref`, so `x` cannot
+// be written to in between
+fn use_ref(_r: &i32) {}
+
+pub fn write_while_shared_borrowed() {
+    let mut x = 0;
+    let r = &x;
+    let n = 1;
+    let _ = n;
+    use_ref(r);
+    x += 1;
+}
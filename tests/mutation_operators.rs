@@ -0,0 +1,261 @@
+#![feature(rustc_private)]
+
+use pcg_mutation_testing::MutatorData;
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+
+// Runs every mutation on the program in `tests/mutants/{file}` with the extra
+// environment variables `envs` and returns the results of each mutation, keyed by
+// its name
+fn run_mutation_testing_on_file(
+    file: &str,
+    envs: &[(&str, &str)],
+) -> HashMap<String, MutatorData> {
+    let workspace_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let path = workspace_dir.join("tests/mutants").join(file);
+    let crate_name = Path::new(file).file_stem().unwrap().to_str().unwrap();
+    let results_dir = std::env::temp_dir().join(format!(
+        "mutation-operators-{}-{}",
+        crate_name,
+        std::process::id()
+    ));
+    std::fs::create_dir_all(&results_dir).unwrap();
+
+    // Results are only written for the primary package of a cargo build
+    let status = Command::new(env!("CARGO_BIN_EXE_mutation_testing_bin"))
+        .arg(&path)
+        .arg("--crate-type=lib")
+        .arg("-Zno-codegen")
+        .arg("--out-dir")
+        .arg(&results_dir)
+        .env("CARGO_CRATE_NAME", crate_name)
+        .env("CARGO_PRIMARY_PACKAGE", "1")
+        .env("RESULTS_DIR", &results_dir)
+        .envs(envs.iter().copied())
+        .status()
+        .unwrap_or_else(|e| panic!("Failed to run mutation testing on {}: {}", file, e));
+    assert!(
+        status.success(),
+        "Mutation testing on {} failed with status: {}",
+        file,
+        status
+    );
+
+    let results_file = File::open(results_dir.join(crate_name).with_extension("json"))
+        .unwrap_or_else(|e| panic!("No results for {}: {}", file, e));
+    let results = serde_json::from_reader(BufReader::new(results_file)).unwrap();
+    std::fs::remove_dir_all(&results_dir).unwrap();
+    results
+}
+
+// Checks that `mutation` creates at least one mutant of the program in
+// `tests/mutants/{file}`, and that the borrow checker accepts or rejects every
+// one of them as the mutation expects
+fn check_mutation(file: &str, mutation: &str) {
+    check_mutation_with_env(file, mutation, &[]);
+}
+
+// Like `check_mutation`, with the extra environment variables `envs`
+fn check_mutation_with_env(file: &str, mutation: &str, envs: &[(&str, &str)]) {
+    let results = run_mutation_testing_on_file(file, envs);
+    let data = results
+        .get(mutation)
+        .unwrap_or_else(|| panic!("{} was not run on {}", mutation, file));
+    assert!(data.instances > 0, "{} created no mutants of {}", mutation, file);
+    assert_eq!(data.invalid, 0, "{} created invalid mutants of {}", mutation, file);
+    assert_eq!(data.panicked, 0, "{} panicked on {}", mutation, file);
+    assert_eq!(
+        data.unexpected, 0,
+        "{} of the {} mutants {} created of {} were not {:?}, error codes: {:?}",
+        data.unexpected, data.instances, mutation, file, data.expected, data.error_codes
+    );
+}
+
+#[test]
+fn use_after_move() {
+    check_mutation("use_after_move.rs", "use-after-move");
+}
+
+#[test]
+fn assign_to_borrowed() {
+    check_mutation("assign_to_borrowed.rs", "assign-to-borrowed");
+}
+
+#[test]
+fn read_from_mutably_lent() {
+    check_mutation("read_from_mutably_lent.rs", "read-from-mutably-lent");
+}
+
+#[test]
+fn double_mut_borrow() {
+    check_mutation("double_mut_borrow.rs", "double-mut-borrow");
+}
+
+#[test]
+fn storage_dead_while_borrowed() {
+    check_mutation("storage_dead_while_borrowed.rs", "storage-dead-while-borrowed");
+}
+
+#[test]
+fn drop_while_borrowed() {
+    check_mutation("drop_while_borrowed.rs", "drop-while-borrowed");
+}
+
+#[test]
+fn escape_via_return() {
+    check_mutation("escape_via_return.rs", "escape-via-return");
+}
+
+#[test]
+fn escape_via_argument() {
+    check_mutation("escape_via_argument.rs", "escape-via-argument");
+}
+
+#[test]
+fn write_through_shared() {
+    check_mutation("write_through_shared.rs", "write-through-shared");
+}
+
+#[test]
+fn move_out_of_deref() {
+    check_mutation("move_out_of_deref.rs", "move-out-of-deref");
+}
+
+#[test]
+fn move_out_of_index() {
+    check_mutation("move_out_of_index.rs", "move-out-of-index");
+}
+
+#[test]
+fn partial_move_then_use_parent() {
+    check_mutation("partial_move_then_use_parent.rs", "partial-move-then-use-parent");
+}
+
+#[test]
+fn partial_move_then_use_sibling() {
+    check_mutation("partial_move_then_use_sibling.rs", "partial-move-then-use-sibling");
+}
+
+#[test]
+fn read_from_readable() {
+    check_mutation("read_from_readable.rs", "read-from-readable");
+}
+
+#[test]
+fn write_to_exclusive() {
+    check_mutation("write_to_exclusive.rs", "write-to-exclusive");
+}
+
+#[test]
+fn mutably_borrow_sibling() {
+    check_mutation("mutably_borrow_sibling.rs", "mutably-borrow-sibling");
+}
+
+#[test]
+fn conflict_with_closure_capture() {
+    check_mutation("conflict_with_closure_capture.rs", "conflict-with-closure-capture");
+}
+
+#[test]
+fn mutate_in_reservation() {
+    check_mutation("mutate_in_reservation.rs", "mutate-in-reservation");
+}
+
+#[test]
+fn read_in_reservation() {
+    check_mutation("read_in_reservation.rs", "read-in-reservation");
+}
+
+#[test]
+fn write_to_relabelled() {
+    check_mutation("write_to_relabelled.rs", "write-to-relabelled");
+}
+
+#[test]
+fn write_through_shared_argument() {
+    check_mutation("write_through_shared_argument.rs", "write-through-shared-argument");
+}
+
+#[test]
+fn write_through_mut_argument() {
+    check_mutation("write_through_mut_argument.rs", "write-through-mut-argument");
+}
+
+#[test]
+fn conflict_with_region_projection() {
+    check_mutation("conflict_with_region_projection.rs", "conflict-with-region-projection");
+}
+
+#[test]
+fn conflict_across_back_edge() {
+    check_mutation("conflict_across_back_edge.rs", "conflict-across-back-edge");
+}
+
+#[test]
+fn mutably_reborrow_shared() {
+    check_mutation("mutably_reborrow_shared.rs", "mutably-reborrow-shared");
+}
+
+#[test]
+fn write_through_shared_reborrow() {
+    check_mutation("write_through_shared_reborrow.rs", "write-through-shared-reborrow");
+}
+
+#[test]
+fn swap_past_release() {
+    check_mutation("swap_past_release.rs", "swap-past-release");
+}
+
+#[test]
+fn use_after_move_into_call() {
+    check_mutation("use_after_move_into_call.rs", "use-after-move");
+}
+
+#[test]
+fn use_after_move_within_statement() {
+    check_mutation_with_env(
+        "use_after_move_within_statement.rs",
+        "use-after-move",
+        &[("MUTATION_WITHIN_STATEMENT", "true")],
+    );
+}
+
+#[test]
+fn write_to_shared() {
+    check_mutation("write_to_shared.rs", "write-to-shared");
+}
+
+#[test]
+fn move_from_borrowed() {
+    check_mutation("move_from_borrowed.rs", "move-from-borrowed");
+}
+
+#[test]
+fn read_from_write_only() {
+    check_mutation("read_from_write_only.rs", "read-from-write-only");
+}
+
+#[test]
+fn mutably_lend_shared() {
+    check_mutation("mutably_lend_shared.rs", "mutably-lend-shared");
+}
+
+#[test]
+fn borrow_expiry_order() {
+    check_mutation("borrow_expiry_order.rs", "borrow-expiry-order");
+}
+
+#[test]
+fn abstract_expiry_order() {
+    check_mutation("abstract_expiry_order.rs", "abstract-expiry-order");
+}
+
+#[test]
+fn assign_to_borrowed_zst() {
+    check_mutation("assign_to_borrowed_zst.rs", "assign-to-borrowed");
+}